@@ -42,6 +42,64 @@ impl BinaryGraph {
     pub fn data(self) -> [usize; MAX_NODES] {
         self.data
     }
+
+    /// The number of bits used by the adjacency data. Since each row only
+    /// holds the node itself and the nodes after it, the cap bit is the
+    /// highest bit of any non-zero row, and so the width can be read off
+    /// the first such row even if some nodes have already been removed.
+    pub fn adjacency_width(&self) -> usize {
+        self.data
+            .iter()
+            .enumerate()
+            .find(|(_, x)| **x != 0)
+            .map(|(i, x)| i + MAX_NODES - x.leading_zeros() as usize)
+            .unwrap_or(0)
+    }
+
+    /// A mask with bit i set if node i is still in the graph.
+    pub fn node_mask(&self) -> usize {
+        self.data
+            .iter()
+            .enumerate()
+            .filter(|(_, x)| **x != 0)
+            .fold(0, |mask, (i, _)| mask | (1 << i))
+    }
+
+    /// Returns the neighbourhood of each node as a mask in which bit j is set
+    /// if the node shares an edge with node j. Unlike the rows of the adjacency
+    /// data, these are symmetric and indexed by node label from the right.
+    pub fn neighbourhoods(&self) -> [usize; MAX_NODES] {
+        let graph_size = self.adjacency_width();
+        let mut neighbourhoods = [0; MAX_NODES];
+        for i in 0..graph_size {
+            for j in (i + 1)..graph_size {
+                if self.data[i] & (1 << (graph_size - j - 1)) != 0 {
+                    neighbourhoods[i] |= 1 << j;
+                    neighbourhoods[j] |= 1 << i;
+                }
+            }
+        }
+        neighbourhoods
+    }
+
+    /// The line graph L(G), which has a node for each edge of G, numbered in
+    /// the order of `edges()`, with two joined when their edges share an end.
+    /// The matchings of G are then the independent sets of L(G).
+    pub fn line_graph(&self) -> BinaryGraph {
+        let edges = self.edges();
+        let line_graph_size = edges.len();
+        assert!(line_graph_size <= MAX_NODES, "the line graph has {} nodes, more than {}", line_graph_size, MAX_NODES);
+        let mut data = [0; MAX_NODES];
+        for (i, (u, v)) in edges.iter().enumerate() {
+            data[i] = 1 << (line_graph_size - i - 1);
+            for (j, (x, y)) in edges.iter().enumerate().skip(i + 1) {
+                if u == x || u == y || v == x || v == y {
+                    data[i] |= 1 << (line_graph_size - j - 1);
+                }
+            }
+        }
+        BinaryGraph::from(data)
+    }
 }
 
 impl Graph for BinaryGraph {
@@ -90,6 +148,21 @@ impl Graph for BinaryGraph {
             .saturating_sub(self.graph_size())
     }
 
+    /// lists the edges as (node1, node2) pairs with node1 < node2, in the
+    /// order in which they appear in the adjacency data
+    fn edges(&self) -> Vec<(usize, usize)> {
+        let graph_size = self.adjacency_width();
+        let mut edges = Vec::new();
+        for i in 0..graph_size {
+            for j in (i + 1)..graph_size {
+                if self.data[i] & (1 << (graph_size - j - 1)) != 0 {
+                    edges.push((i, j));
+                }
+            }
+        }
+        edges
+    }
+
     /// checks whether the graph is edgeless, i.e. if each of the elements
     /// is a power of two or 0
     fn edgeless(&self) -> bool {
@@ -0,0 +1,353 @@
+use crate::binary_graph_matching::BinaryGraph;
+use std::mem::size_of;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+const POLY_SIZE: usize = size_of::<usize>()*8;
+
+// The splitting at components, bridges and cut vertices lives in its own
+// engine, `calculate_matching_polynomial_decomposed`, rather than inside the
+// existing ones. The pointer, address and adaptive engines add into a single
+// polynomial at the leaves of the edge-deletion tree, while a split needs the
+// polynomials of its parts back so that it can multiply them, so those engines
+// are left as they are and do not detect bridges or cut vertices. Callers who
+// want the splits, and the report of what they saved, use this engine (or its
+// modular variant in `modular.rs`) directly.
+
+/// A record of how the decomposing engine split its work. Every leaf of the
+/// plain edge-deletion tree is one matching of the graph, so for a graph
+/// with Z matchings (its Hosoya index) that tree visits 2Z - 1 subgraphs; the
+/// difference from `subgraphs_visited` is the part of the tree that was
+/// avoided. The count saturates at u64::MAX for graphs with more matchings
+/// than that.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecompositionReport {
+    pub subgraphs_visited: u64,
+    pub edge_deletion_subgraphs: u64,
+    pub component_splits: u64,
+    pub bridge_splits: u64,
+    pub cut_vertex_splits: u64,
+    pub edge_deletions: u64,
+}
+
+impl DecompositionReport {
+    pub fn subgraphs_avoided(&self) -> u64 {
+        self.edge_deletion_subgraphs.saturating_sub(self.subgraphs_visited)
+    }
+}
+
+/// Calculates the matching polynomial by edge deletion, splitting the graph
+/// wherever the current subgraph allows it; see
+/// `calculate_matching_polynomial_decomposed_with_report`.
+pub fn calculate_matching_polynomial_decomposed(graph: BinaryGraph) -> [u64; POLY_SIZE] {
+    calculate_matching_polynomial_decomposed_with_report(graph).0
+}
+
+/// Calculates the matching polynomial, splitting the current subgraph G
+/// wherever it can. If G is disconnected, m(G) is the product over its
+/// components. If G has a bridge uv, with H1 containing u and H2 containing v
+/// the two sides of it,
+///     m(G) = m(H1) m(H2) + m(H1 - u) m(H2 - v)
+/// and if G has a cut vertex v, with G1 and G2 the two parts sharing v,
+///     m(G) = m(G1) m(G2 - v) + m(G1 - v) m(G2) - x m(G1 - v) m(G2 - v).
+/// Otherwise G is biconnected, and we delete an edge at a node of highest
+/// degree, as the other engines do.
+///
+/// Among the bridges and cut vertices we take the most balanced split. The
+/// report counts the splits made and compares the subgraphs visited with
+/// those of plain edge deletion.
+pub fn calculate_matching_polynomial_decomposed_with_report(graph: BinaryGraph) -> ([u64; POLY_SIZE], DecompositionReport) {
+    let mut report = DecompositionReport::default();
+    let coefficients = decomposed_polynomial(&graph.neighbourhoods(), graph.node_mask(), None, &mut report);
+
+    let mut poly = [0; POLY_SIZE];
+    poly[..coefficients.len()].copy_from_slice(&coefficients);
+    // the Hosoya index of a graph of many dense blocks passes 2^63 well before
+    // its polynomial leaves a u64, so this count saturates
+    let hosoya_index = coefficients.iter().fold(0u64, |sum, x| sum.saturating_add(*x));
+    report.edge_deletion_subgraphs = hosoya_index.checked_mul(2).map_or(u64::MAX, |x| x - 1);
+    (poly, report)
+}
+
+/// The ways of splitting a connected subgraph
+enum Split {
+    Bridge(usize, usize, usize),
+    CutVertex(usize, usize),
+}
+
+/// The matching polynomial of the subgraph induced on `nodes`, with the
+/// coefficients reduced modulo `modulus` if one is given. The splits multiply
+/// polynomials, so unlike the leaf-counting engines, this can reach
+/// coefficients beyond a u64 long before the recursion becomes infeasible.
+pub(super) fn decomposed_polynomial(neighbourhoods: &[usize; MAX_NODES], nodes: usize, modulus: Option<u64>,
+                                    report: &mut DecompositionReport) -> Vec<u64> {
+    report.subgraphs_visited += 1;
+    let node_count = nodes.count_ones() as usize;
+    if edgeless(neighbourhoods, nodes) {
+        let mut poly = vec![0; node_count + 1];
+        poly[node_count] = 1;
+        return poly
+    }
+
+    let components = components(neighbourhoods, nodes);
+    if components.len() > 1 {
+        report.component_splits += 1;
+        return components
+            .into_iter()
+            .map(|component| decomposed_polynomial(neighbourhoods, component, modulus, report))
+            .fold(vec![1], |poly, other| multiply(&poly, &other, modulus))
+    }
+
+    match best_split(neighbourhoods, nodes) {
+        Some(Split::Bridge(u, v, side)) => {
+            report.bridge_splits += 1;
+            let h1 = nodes & !side;
+            let h2 = side;
+            let first = multiply(&decomposed_polynomial(neighbourhoods, h1, modulus, report),
+                                 &decomposed_polynomial(neighbourhoods, h2, modulus, report), modulus);
+            let second = multiply(&decomposed_polynomial(neighbourhoods, h1 & !(1 << u), modulus, report),
+                                  &decomposed_polynomial(neighbourhoods, h2 & !(1 << v), modulus, report), modulus);
+            add(&first, &second, modulus)
+        },
+        Some(Split::CutVertex(v, side)) => {
+            report.cut_vertex_splits += 1;
+            let g1 = side | (1 << v);
+            let g2 = nodes & !side;
+            let g1_poly = decomposed_polynomial(neighbourhoods, g1, modulus, report);
+            let g2_poly = decomposed_polynomial(neighbourhoods, g2, modulus, report);
+            let a = decomposed_polynomial(neighbourhoods, side, modulus, report);
+            let b = decomposed_polynomial(neighbourhoods, g2 & !(1 << v), modulus, report);
+
+            // the subtraction is exact, so we add first to stay non-negative
+            let poly = add(&multiply(&g1_poly, &b, modulus), &multiply(&a, &g2_poly, modulus), modulus);
+            let mut shifted = vec![0];
+            shifted.extend(multiply(&a, &b, modulus));
+            subtract(&poly, &shifted, modulus)
+        },
+        None => {
+            report.edge_deletions += 1;
+            let node = (0..MAX_NODES)
+                .filter(|node| nodes & (1 << node) != 0)
+                .max_by_key(|node| ((neighbourhoods[*node] & nodes).count_ones(), MAX_NODES - node))
+                .unwrap();
+            let partner = (neighbourhoods[node] & nodes).trailing_zeros() as usize;
+
+            // G' = G - e and G'' = G - {u, v}
+            let mut graph_prime = *neighbourhoods;
+            graph_prime[node] &= !(1 << partner);
+            graph_prime[partner] &= !(1 << node);
+            let sub_nodes = nodes & !(1 << node) & !(1 << partner);
+            let poly = decomposed_polynomial(&graph_prime, nodes, modulus, report);
+            let sub_poly = decomposed_polynomial(neighbourhoods, sub_nodes, modulus, report);
+            add(&poly, &sub_poly, modulus)
+        },
+    }
+}
+
+fn edgeless(neighbourhoods: &[usize; MAX_NODES], nodes: usize) -> bool {
+    (0..MAX_NODES).all(|node| nodes & (1 << node) == 0 || neighbourhoods[node] & nodes == 0)
+}
+
+fn components(neighbourhoods: &[usize; MAX_NODES], nodes: usize) -> Vec<usize> {
+    let mut components = Vec::new();
+    let mut remaining_nodes = nodes;
+    while remaining_nodes != 0 {
+        let mut component: usize = 1 << remaining_nodes.trailing_zeros();
+        let mut frontier = component;
+        while frontier != 0 {
+            let node = frontier.trailing_zeros() as usize;
+            frontier &= frontier - 1;
+            let new_nodes = neighbourhoods[node] & nodes & !component;
+            component |= new_nodes;
+            frontier |= new_nodes;
+        }
+        remaining_nodes &= !component;
+        components.push(component);
+    }
+    components
+}
+
+/// Finds the bridges and cut vertices of a connected subgraph with Tarjan's
+/// lowpoint search, and returns the most balanced split among them, i.e. the
+/// one whose larger part is smallest, preferring bridges.
+fn best_split(neighbourhoods: &[usize; MAX_NODES], nodes: usize) -> Option<Split> {
+    struct Search<'a> {
+        neighbourhoods: &'a [usize; MAX_NODES],
+        nodes: usize,
+        time: usize,
+        discovered: [usize; MAX_NODES],
+        low: [usize; MAX_NODES],
+        subtree: [usize; MAX_NODES],
+        best: Option<(usize, Split)>,
+    }
+
+    impl Search<'_> {
+        fn consider(&mut self, larger_part: usize, split: Split) {
+            if self.best.as_ref().is_none_or(|(best_part, _)| larger_part < *best_part) {
+                self.best = Some((larger_part, split));
+            }
+        }
+
+        fn visit(&mut self, node: usize, parent: Option<usize>) {
+            self.time += 1;
+            self.discovered[node] = self.time;
+            self.low[node] = self.time;
+            self.subtree[node] = 1 << node;
+            let mut neighbours = self.neighbourhoods[node] & self.nodes;
+            while neighbours != 0 {
+                let next = neighbours.trailing_zeros() as usize;
+                neighbours &= neighbours - 1;
+                if Some(next) == parent {
+                    continue
+                }
+                if self.discovered[next] != 0 {
+                    self.low[node] = self.low[node].min(self.discovered[next]);
+                    continue
+                }
+                self.visit(next, Some(node));
+                self.subtree[node] |= self.subtree[next];
+                self.low[node] = self.low[node].min(self.low[next]);
+
+                let side = self.subtree[next];
+                let side_count = side.count_ones() as usize;
+                let total = self.nodes.count_ones() as usize;
+                // splitting off a single node saves nothing over deleting
+                // its edges, so each part has to keep two nodes or more
+                if self.low[next] > self.discovered[node] && side_count >= 2 && total - side_count >= 2 {
+                    self.consider(side_count.max(total - side_count), Split::Bridge(node, next, side));
+                }
+                if self.low[next] >= self.discovered[node] && side_count >= 2 && total - side_count > 2 {
+                    // counted one larger, so that a bridge wins a tie
+                    let larger_part = (side_count + 1).max(total - side_count);
+                    self.consider(larger_part + 1, Split::CutVertex(node, side));
+                }
+            }
+        }
+    }
+
+    let mut search = Search {
+        neighbourhoods,
+        nodes,
+        time: 0,
+        discovered: [0; MAX_NODES],
+        low: [0; MAX_NODES],
+        subtree: [0; MAX_NODES],
+        best: None,
+    };
+    search.visit(nodes.trailing_zeros() as usize, None);
+    search.best.map(|(_, split)| split)
+}
+
+fn multiply(poly: &[u64], other: &[u64], modulus: Option<u64>) -> Vec<u64> {
+    let mut product = vec![0; poly.len() + other.len() - 1];
+    for (i, x) in poly.iter().enumerate() {
+        for (j, y) in other.iter().enumerate() {
+            product[i + j] = match modulus {
+                None => product[i + j] + x * y,
+                Some(modulus) => ((product[i + j] as u128 + *x as u128 * *y as u128) % modulus as u128) as u64,
+            };
+        }
+    }
+    product
+}
+
+fn add(poly: &[u64], other: &[u64], modulus: Option<u64>) -> Vec<u64> {
+    let mut sum = vec![0; poly.len().max(other.len())];
+    for (i, x) in poly.iter().enumerate() {
+        sum[i] += x;
+    }
+    for (i, y) in other.iter().enumerate() {
+        sum[i] += y;
+    }
+    if let Some(modulus) = modulus {
+        sum.iter_mut().for_each(|x| *x %= modulus);
+    }
+    sum
+}
+
+/// poly - other, where other is no longer than poly and, without a modulus,
+/// the difference is known to be non-negative
+fn subtract(poly: &[u64], other: &[u64], modulus: Option<u64>) -> Vec<u64> {
+    let mut difference = poly.to_vec();
+    difference.iter_mut()
+        .zip(other.iter())
+        .for_each(|(x, y)| *x = match modulus {
+            None => *x - y,
+            Some(modulus) => (*x + modulus - y % modulus) % modulus,
+        });
+    difference
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_matching_polynomial_pointer;
+
+    #[test]
+    fn test_decomposed_against_pointer() {
+        let standard_data = [
+            0b11001, 0b1001, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        // the 5-cycle with a pendant node
+        let pendant_data = [
+            0b110010, 0b11000, 0b1100, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        // two triangles sharing node 2, with a separate edge 5-6
+        let bowtie_data = [
+            0b1110000, 0b110000, 0b11100, 0b1100, 0b100, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        for data in [standard_data, pendant_data, bowtie_data] {
+            let graph = BinaryGraph::from(data);
+            let (matching_poly, report) = calculate_matching_polynomial_decomposed_with_report(graph);
+            assert_eq!(matching_poly, calculate_matching_polynomial_pointer(graph));
+            assert!(report.subgraphs_visited <= report.edge_deletion_subgraphs);
+        }
+    }
+
+    #[test]
+    fn test_decomposition_report() {
+        // the path on 16 nodes is all bridges, so splitting it in the middle
+        // each time visits far fewer subgraphs than the 2 * 1597 - 1 of the
+        // edge-deletion tree
+        let mut chain_data = [0; MAX_NODES];
+        for (i, row) in chain_data.iter_mut().enumerate().take(16) {
+            *row = if i < 15 { 0b11 << (14 - i) } else { 1 };
+        }
+        let graph = BinaryGraph::from(chain_data);
+        let (matching_poly, report) = calculate_matching_polynomial_decomposed_with_report(graph);
+        assert_eq!(matching_poly, calculate_matching_polynomial_pointer(graph));
+        assert_eq!(report.edge_deletion_subgraphs, 2 * 1597 - 1);
+        assert!(report.bridge_splits > 0);
+        assert!(report.subgraphs_avoided() > 2000);
+
+        // five disjoint copies of K_10 have over 2^64 matchings, though every
+        // coefficient of m(K_10)^5 still fits in a u64
+        let mut blocks_data = [0; MAX_NODES];
+        for (node, row) in blocks_data.iter_mut().enumerate().take(50) {
+            let block_end = (node / 10 + 1) * 10;
+            *row = ((1 << (block_end - node)) - 1) << (50 - block_end);
+        }
+        let mut block_data = [0; MAX_NODES];
+        block_data[..10].copy_from_slice(&blocks_data[40..50]);
+        let block_poly = calculate_matching_polynomial_pointer(BinaryGraph::from(block_data));
+        let mut expected = vec![1u128];
+        for _ in 0..5 {
+            let mut product = vec![0; expected.len() + 10];
+            for (i, x) in expected.iter().enumerate() {
+                for (j, y) in block_poly[..=10].iter().enumerate() {
+                    product[i + j] += x * *y as u128;
+                }
+            }
+            expected = product;
+        }
+        let (matching_poly, report) = calculate_matching_polynomial_decomposed_with_report(BinaryGraph::from(blocks_data));
+        assert_eq!(matching_poly[..=50].iter().map(|x| *x as u128).collect::<Vec<_>>(), expected);
+        assert_eq!(report.edge_deletion_subgraphs, u64::MAX);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::binary_graph_matching::BinaryGraph;
+use crate::traits::Graph;
+
+/// A lazy iterator over the matchings of a graph. It walks the same G'/G''
+/// tree as `calculate_matching_polynomial_pointer_addresses`: every leaf of
+/// the tree is one matching, made up of the edges along which the path to the
+/// leaf took the G'' branch. Rather than collecting the addresses first, we
+/// keep an explicit stack of the unvisited branches, so only the current path
+/// is held in memory.
+///
+/// Each matching is yielded as a list of (u, v) pairs, with u < v, in the
+/// node labels of the original graph.
+#[derive(Debug, Clone)]
+pub struct Matchings {
+    stack: Vec<(BinaryGraph, Vec<(usize, usize)>)>,
+    size: Option<usize>,
+}
+
+impl Matchings {
+    fn new(graph: BinaryGraph, size: Option<usize>) -> Matchings {
+        Matchings {
+            stack: vec![(graph, Vec::new())],
+            size,
+        }
+    }
+}
+
+impl Iterator for Matchings {
+    type Item = Vec<(usize, usize)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((graph, matching)) = self.stack.pop() {
+            if let Some(size) = self.size {
+                // once the matching is big enough, the only way on is to
+                // delete every remaining edge; if it can no longer grow big
+                // enough, nothing below this branch is wanted.
+                if matching.len() == size {
+                    return Some(matching)
+                }
+                if matching.len() + graph.graph_size() / 2 < size {
+                    continue
+                }
+            }
+            if graph.edgeless() {
+                if self.size.is_none() {
+                    return Some(matching)
+                }
+                continue
+            }
+
+            let (start_node, end_node, _) = graph.get_relevant_edge();
+            let (graph_prime, graph_prime_prime) = graph.get_graph_primes();
+            let mut new_matching = matching.clone();
+            new_matching.push((start_node, end_node));
+
+            // push G' first so that G'' is visited first, as in the addresses
+            self.stack.push((graph_prime, matching));
+            self.stack.push((graph_prime_prime, new_matching));
+        }
+        None
+    }
+}
+
+/// Iterates over every matching of the graph, including the empty one.
+pub fn matchings(graph: BinaryGraph) -> Matchings {
+    Matchings::new(graph, None)
+}
+
+/// Iterates over the matchings with exactly `size` edges.
+pub fn matchings_of_size(graph: BinaryGraph, size: usize) -> Matchings {
+    Matchings::new(graph, Some(size))
+}
+
+/// Iterates over the perfect matchings. A graph with an odd number of nodes
+/// has none, so the iterator is empty.
+pub fn perfect_matchings(graph: BinaryGraph) -> Matchings {
+    let graph_size = graph.graph_size();
+    if graph_size % 2 == 1 {
+        return Matchings {
+            stack: Vec::new(),
+            size: None,
+        }
+    }
+    Matchings::new(graph, Some(graph_size / 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_matching_polynomial_pointer;
+
+    #[test]
+    fn test_matchings_against_polynomial() {
+        let standard_data = [
+            0b11001, 0b1001, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        let graph = BinaryGraph::from(standard_data);
+        let graph_size = graph.graph_size();
+        let matching_polynomial = calculate_matching_polynomial_pointer(graph);
+
+        let mut counts = [0; 3];
+        for matching in matchings(graph) {
+            counts[matching.len()] += 1;
+        }
+        for (k, count) in counts.iter().enumerate() {
+            assert_eq!(*count, matching_polynomial[graph_size - 2 * k]);
+            assert_eq!(matchings_of_size(graph, k).count() as u64, *count);
+        }
+    }
+
+    #[test]
+    fn test_perfect_matchings() {
+        let chain_data = [
+            0b1100, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        let graph = BinaryGraph::from(chain_data);
+        let mut perfect = perfect_matchings(graph).collect::<Vec<_>>();
+        perfect.iter_mut().for_each(|matching| matching.sort());
+        assert_eq!(perfect, vec![vec![(0, 1), (2, 3)]]);
+
+        let fc_data = [
+            0b111111, 0b11111, 0b1111, 0b111, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let graph = BinaryGraph::from(fc_data);
+        let mut count = 0;
+        for matching in perfect_matchings(graph) {
+            let covered = matching
+                .iter()
+                .fold(0, |mask, (u, v)| mask | (1 << u) | (1 << v));
+            assert_eq!(covered, 0b111111);
+            count += 1;
+        }
+        assert_eq!(count, 15);
+    }
+}
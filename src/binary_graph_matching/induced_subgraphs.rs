@@ -0,0 +1,208 @@
+use crate::binary_graph_matching::BinaryGraph;
+use crate::traits::Graph;
+use std::collections::HashMap;
+use std::mem::size_of;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+const POLY_SIZE: usize = size_of::<usize>()*8;
+
+/// A memo table holding the matching polynomials of the induced subgraphs of
+/// a graph, keyed by the mask of nodes they keep. Polynomials are built with
+/// the vertex recurrence on the first remaining node v:
+///     m(S, x) = x m(S - v, x) + sum_{u ~ v} m(S - v - u, x)
+/// (signless, as in the other engines). Every subgraph reached this way is
+/// itself an induced subgraph, so the table can be shared between the graph
+/// and any of its vertex-deleted subgraphs, e.g. the cards of its deck or the
+/// G - {u, v} graphs needed for its edge deck.
+#[derive(Debug, Clone)]
+pub struct InducedSubgraphTable {
+    neighbourhoods: [usize; MAX_NODES],
+    node_mask: usize,
+    polynomials: HashMap<usize, Vec<u64>>,
+}
+
+impl InducedSubgraphTable {
+    pub fn new(graph: &BinaryGraph) -> InducedSubgraphTable {
+        InducedSubgraphTable {
+            neighbourhoods: graph.neighbourhoods(),
+            node_mask: graph.node_mask(),
+            polynomials: HashMap::new(),
+        }
+    }
+
+    /// The mask of the nodes in the whole graph
+    pub fn node_mask(&self) -> usize {
+        self.node_mask
+    }
+
+    /// The neighbourhood of each node of the whole graph
+    pub fn neighbourhoods(&self) -> &[usize; MAX_NODES] {
+        &self.neighbourhoods
+    }
+
+    /// The number of induced subgraphs whose polynomials are stored
+    pub fn len(&self) -> usize {
+        self.polynomials.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.polynomials.is_empty()
+    }
+
+    /// Returns the matching polynomial of the subgraph induced by the nodes in
+    /// the mask, with coefficients indexed by the power of x.
+    pub fn matching_polynomial(&mut self, nodes: usize) -> [u64; POLY_SIZE] {
+        let mut poly = [0; POLY_SIZE];
+        let coefficients = self._matching_polynomial(nodes & self.node_mask);
+        poly[..coefficients.len()].copy_from_slice(&coefficients);
+        poly
+    }
+
+    fn _matching_polynomial(&mut self, nodes: usize) -> Vec<u64> {
+        if nodes == 0 {
+            return vec![1]
+        }
+        if let Some(poly) = self.polynomials.get(&nodes) {
+            return poly.clone()
+        }
+
+        let node = nodes.trailing_zeros() as usize;
+        let rest = nodes & !(1 << node);
+
+        // x m(S - v)
+        let mut poly = vec![0];
+        poly.extend(self._matching_polynomial(rest));
+
+        // + m(S - v - u) for each neighbour u of v
+        let mut partners = self.neighbourhoods[node] & rest;
+        while partners != 0 {
+            let partner = partners.trailing_zeros() as usize;
+            partners &= partners - 1;
+            let sub_poly = self._matching_polynomial(rest & !(1 << partner));
+            poly.iter_mut()
+                .zip(sub_poly.iter())
+                .for_each(|(x, y)| *x += y);
+        }
+        self.polynomials.insert(nodes, poly.clone());
+        poly
+    }
+}
+
+/// Calculates the matching polynomial with the memoised vertex recurrence.
+/// This is mostly of use when the polynomials of several induced subgraphs are
+/// wanted together; see `InducedSubgraphTable`.
+pub fn calculate_matching_polynomial_memoised(graph: BinaryGraph) -> [u64; POLY_SIZE] {
+    let mut table = InducedSubgraphTable::new(&graph);
+    table.matching_polynomial(graph.node_mask())
+}
+
+/// Calculates m(G) together with m(G - v) for every node v, in node order,
+/// i.e. the polynomial of the graph and of each card of its deck. All of them
+/// are induced subgraphs, so they are read from a single memo table rather
+/// than computed by n + 1 separate runs of the recursion.
+pub fn matching_polynomial_with_deck(graph: BinaryGraph) -> ([u64; POLY_SIZE], Vec<[u64; POLY_SIZE]>) {
+    let mut table = InducedSubgraphTable::new(&graph);
+    let node_mask = table.node_mask();
+    let matching_poly = table.matching_polynomial(node_mask);
+
+    let mut remaining_nodes = node_mask;
+    let mut deck_polies = Vec::new();
+    while remaining_nodes != 0 {
+        let node = remaining_nodes.trailing_zeros() as usize;
+        remaining_nodes &= remaining_nodes - 1;
+        deck_polies.push(table.matching_polynomial(node_mask & !(1 << node)));
+    }
+    (matching_poly, deck_polies)
+}
+
+/// m(G), and each edge e with m(G - e)
+pub type EdgeDeckPolynomials = ([u64; POLY_SIZE], Vec<((usize, usize), [u64; POLY_SIZE])>);
+
+/// Calculates m(G - e) for every edge e of the graph, along with m(G). Rather
+/// than running the recursion once per edge, this uses the edge recurrence
+/// the other way round:
+///     m(G - e, x) = m(G, x) - m(G - {u, v}, x)
+/// where each G - {u, v} is an induced subgraph, and so shares the memo table
+/// used for m(G).
+pub fn calculate_edge_deck_polynomials(graph: BinaryGraph) -> EdgeDeckPolynomials {
+    let mut table = InducedSubgraphTable::new(&graph);
+    let node_mask = table.node_mask();
+    let matching_poly = table.matching_polynomial(node_mask);
+
+    let edge_deck_polies = graph
+        .edges()
+        .into_iter()
+        .map(|(node1, node2)| {
+            let sub_poly = table.matching_polynomial(node_mask & !(1 << node1) & !(1 << node2));
+            let mut poly = matching_poly;
+            poly.iter_mut()
+                .zip(sub_poly.iter())
+                .for_each(|(x, y)| *x -= y);
+            ((node1, node2), poly)
+        })
+        .collect::<Vec<_>>();
+    (matching_poly, edge_deck_polies)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_matching_polynomial_pointer;
+    use crate::traits::{get_deck, get_edge_deck};
+
+    #[test]
+    fn test_memoised_polynomial() {
+        let standard_data = [
+            0b11001, 0b1001, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        let graph = BinaryGraph::from(standard_data);
+        assert_eq!(calculate_matching_polynomial_memoised(graph), calculate_matching_polynomial_pointer(graph));
+    }
+
+    #[test]
+    fn test_matching_polynomial_with_deck() {
+        let chain_data = [
+            0b1100000, 0b110000, 0b11000, 0b1100, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+        ];
+        let fc_data = [
+            0b111111, 0b11111, 0b1111, 0b111, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        for data in [chain_data, fc_data] {
+            let graph = BinaryGraph::from(data);
+            let (matching_poly, deck_polies) = matching_polynomial_with_deck(graph);
+            assert_eq!(matching_poly, calculate_matching_polynomial_pointer(graph));
+
+            let deck = get_deck(graph);
+            assert_eq!(deck.len(), deck_polies.len());
+            for (card, poly) in deck.into_iter().zip(deck_polies.iter()) {
+                assert_eq!(&calculate_matching_polynomial_pointer(card), poly);
+            }
+        }
+    }
+
+    #[test]
+    fn test_edge_deck_polynomials() {
+        // the 5-cycle with a pendant node
+        let data = [
+            0b110010, 0b11000, 0b1100, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let graph = BinaryGraph::from(data);
+        let (matching_poly, edge_deck_polies) = calculate_edge_deck_polynomials(graph);
+        assert_eq!(matching_poly, calculate_matching_polynomial_pointer(graph));
+
+        let edge_deck = get_edge_deck(graph);
+        assert_eq!(edge_deck.len(), edge_deck_polies.len());
+        for (card, (_, poly)) in edge_deck.into_iter().zip(edge_deck_polies.iter()) {
+            assert_eq!(&calculate_matching_polynomial_pointer(card), poly);
+        }
+    }
+}
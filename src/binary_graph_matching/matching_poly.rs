@@ -1,4 +1,4 @@
-use crate::{binary_graph_matching::BinaryGraph, polynomials::poly2herme, polynomials::herme2poly};
+use crate::{binary_graph_matching::BinaryGraph, polynomials::herme_table};
 use std::mem::size_of;
 use crate::traits::Graph;
 use polynomial::Polynomial;
@@ -19,6 +19,8 @@ fn ceil(x: i64, y: i64) -> i64 {
 /// memory.
 const POLY_SIZE: usize = size_of::<usize>()*8;
 
+/// The plain edge-deletion engine. It does not split the graph at bridges
+/// or cut vertices; `calculate_matching_polynomial_decomposed` does.
 pub fn calculate_matching_polynomial_pointer(graph: BinaryGraph) -> [u64; size_of::<usize>()*8] {
         let poly: &mut [u64; POLY_SIZE] = &mut [0; POLY_SIZE];
         for i in 0..POLY_SIZE {
@@ -40,31 +42,77 @@ pub fn calculate_matching_polynomial_pointer_addresses(graph: BinaryGraph) -> ([
         return (*poly, addresses)
 }
 
+/// How the adaptive engine decides to move between the graph and its
+/// complement. Under `Density`, the engine switches once the density of the
+/// subgraph in hand reaches `density_threshold`. Under
+/// `EstimatedSubtreeSize` it switches when the complement looks to have the
+/// smaller edge-deletion tree, and the threshold is not used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwitchRule {
+    Density,
+    EstimatedSubtreeSize,
+}
+
+/// The settings of the adaptive engine. If `allow_switch_back` is false, the
+/// engine switches to the complement at most once on any path, as it always
+/// used to; otherwise it checks the rule again on every subgraph, in either
+/// direction, but only ever switches to the side with fewer edges, so that a
+/// density threshold below 0.5 then acts as 0.5.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdaptiveConfig {
+    pub density_threshold: f32,
+    pub allow_switch_back: bool,
+    pub rule: SwitchRule,
+}
+
+impl Default for AdaptiveConfig {
+    fn default() -> Self {
+        AdaptiveConfig {
+            density_threshold: 0.5,
+            allow_switch_back: false,
+            rule: SwitchRule::Density,
+        }
+    }
+}
+
+/// A record of the switches made by the adaptive engine: entry k of
+/// `switch_histogram` is the number of root-to-leaf paths of the recursion
+/// along which the engine switched k times.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdaptiveReport {
+    pub switch_histogram: Vec<u64>,
+}
+
+impl AdaptiveReport {
+    pub fn paths(&self) -> u64 {
+        self.switch_histogram.iter().sum()
+    }
+
+    pub fn max_switches(&self) -> usize {
+        self.switch_histogram.len().saturating_sub(1)
+    }
+}
+
 pub fn calculate_matching_polynomial_adaptive(graph: BinaryGraph) -> [i64; POLY_SIZE] {
+        calculate_matching_polynomial_adaptive_with_config(graph, AdaptiveConfig::default()).0
+}
+
+pub fn calculate_matching_polynomial_adaptive_with_config(graph: BinaryGraph, config: AdaptiveConfig) -> ([i64; POLY_SIZE], AdaptiveReport) {
         let poly: &mut [i64; POLY_SIZE] = &mut [0; POLY_SIZE];
-        let hermites: &mut [f32; POLY_SIZE * POLY_SIZE] = &mut [0.0; POLY_SIZE * POLY_SIZE];
-
-        // cache the Hermite polynomials
-        for i in 0..graph.initial_graph_size() + 1 {
-            // make a monomial that is of order i
-            let data = [0.0; POLY_SIZE];
-            let mut coeffics = data[..i].to_vec();
-            coeffics.push(1.0);
-            let monomial = Polynomial::new(coeffics);
-
-            // and treat it like it is a Hermite polynomial
-            let hermite = herme2poly(&monomial);
-            hermites[i*POLY_SIZE..i*POLY_SIZE + i + 1].copy_from_slice(&hermite.data());
-        }
+        let mut report = AdaptiveReport::default();
+
+        // cache the Hermite polynomials, exactly; row i holds He_i
+        let hermites = herme_table(graph.initial_graph_size() + 1);
 
-        // set the blank polynomial
-        for i in 0..POLY_SIZE {
-            poly[i] = 0;
-        }
-        
         // now run the recursive function that does it
-        _calculate_matching_polynomial_static_adaptive(graph, poly, false, hermites, 1);
-        return *poly
+        let state = AdaptiveState {
+            complement: false,
+            sign_coeffic: 1,
+            base_size: graph.initial_graph_size(),
+            switches: 0,
+        };
+        _calculate_matching_polynomial_static_adaptive(graph, poly, state, &hermites, &config, &mut report);
+        (*poly, report)
 }
 
 // From here are the recursive functions called by the functions above. 
@@ -94,43 +142,107 @@ fn _calculate_matching_polynomial_static_addresses<T: Graph>(graph: T, poly: &mu
     }
 }
 
+/// Where the adaptive recursion stands on the current path. In the standard
+/// basis, a leaf of size s contributes sign_coeffic (-1)^((base_size - s)/2)
+/// x^s; in the complement, where the recursion is run on the complement of
+/// the graph and the matchings of that are counted by
+///     m(G) = sum_k p(complement of G, k) He_(n - 2k)
+/// a leaf of size s contributes sign_coeffic He_s.
+#[derive(Debug, Clone, Copy)]
+struct AdaptiveState {
+    complement: bool,
+    sign_coeffic: i64,
+    base_size: usize,
+    switches: usize,
+}
+
 /// the following function, aimed at being a drop-in replacement for
 /// calculate_matching_polynomial_static, assumes that the graph is to have its
 /// polynomial calculated adaptively as the density of the relevant subraph
 /// changes over the course of the algorithm.
-fn _calculate_matching_polynomial_static_adaptive<T: Graph>(mut graph: T, poly: &mut [i64; POLY_SIZE], mut complement: bool, hermites: &[f32; POLY_SIZE * POLY_SIZE], mut sign_coeffic: i64) {
-    let graph_density = graph.density();
-    if graph_density >= 0.5 && !complement {
-        complement = true;
-        sign_coeffic = (-1 as i64).pow((graph.initial_graph_size() as u32 - graph.graph_size() as u32)/2) as i64;
+fn _calculate_matching_polynomial_static_adaptive<T: Graph>(mut graph: T, poly: &mut [i64; POLY_SIZE], mut state: AdaptiveState, hermites: &[i64], config: &AdaptiveConfig, report: &mut AdaptiveReport) {
+    // an edgeless graph is already a leaf, so there is nothing to gain by
+    // switching it. When switching back is allowed we also only ever switch
+    // to fewer edges, as otherwise an edge deleted on one side could be put
+    // back on the other and the recursion need never end.
+    let graph_size = graph.graph_size();
+    let edge_count = graph.edge_count();
+    let complement_edge_count = (graph_size * graph_size.saturating_sub(1) / 2).saturating_sub(edge_count);
+    let may_switch = match config.allow_switch_back {
+        true => complement_edge_count < edge_count,
+        false => !state.complement,
+    };
+    if may_switch && edge_count > 0 && should_switch(&graph, config) {
+        if state.complement {
+            state.base_size = graph_size;
+        } else {
+            state.sign_coeffic *= (-1 as i64).pow((state.base_size as u32 - graph_size as u32)/2);
+        }
+        state.complement = !state.complement;
+        state.switches += 1;
         graph = graph.complement();
     }
     if graph.edgeless() {
         let graph_size = graph.graph_size();
-        if complement {
-            // run the update as if in the Hermite basis
-            let hermite_coeffics = hermites[(graph_size) * POLY_SIZE..(graph_size) * POLY_SIZE + graph_size + 1].to_vec();
+        if state.complement {
+            // run the update as if in the Hermite basis. The table is exact
+            // modulo 2^64, so wrapping here leaves the result exact whenever
+            // it fits in an i64.
+            let table_size = graph.initial_graph_size() + 1;
+            let hermite_coeffics = &hermites[graph_size * table_size..graph_size * table_size + graph_size + 1];
             for i in 0..graph_size + 1 {
-                poly[i] += sign_coeffic * hermite_coeffics[i] as i64;
+                poly[i] = poly[i].wrapping_add(state.sign_coeffic.wrapping_mul(hermite_coeffics[i]));
             }
         } else {
                 // run the update as if in the standard basis
-                poly[graph_size] += (-1 as i64).pow((graph.initial_graph_size() as u32 - graph_size as u32)/2);
+                poly[graph_size] += state.sign_coeffic * (-1 as i64).pow((state.base_size as u32 - graph_size as u32)/2);
         }
+        if report.switch_histogram.len() <= state.switches {
+            report.switch_histogram.resize(state.switches + 1, 0);
+        }
+        report.switch_histogram[state.switches] += 1;
     } else {
         let (graph_prime, graph_prime_prime) = graph.get_graph_primes();
         _calculate_matching_polynomial_static_adaptive(graph_prime_prime,
                                                            poly,
-                                                           complement,
+                                                           state,
                                                            hermites, 
-                                                           sign_coeffic);
+                                                           config,
+                                                           report);
         // Handle graph_prime
         _calculate_matching_polynomial_static_adaptive(graph_prime,
                                                        poly,
-                                                       complement,
+                                                       state,
                                                        hermites, 
-                                                       sign_coeffic);
+                                                       config,
+                                                       report);
+    }
+}
+
+fn should_switch<T: Graph>(graph: &T, config: &AdaptiveConfig) -> bool {
+    match config.rule {
+        SwitchRule::Density => graph.density() >= config.density_threshold,
+        SwitchRule::EstimatedSubtreeSize => {
+            estimated_subtree_size(&graph.complement()) < estimated_subtree_size(graph)
+        }
+    }
+}
+
+/// A rough guide to the size of the edge-deletion tree below a graph, which
+/// has one leaf per matching: the log of the product over the nodes of
+/// sqrt(degree + 1). This is exact for a perfect matching and grows with the
+/// degrees as the number of matchings does.
+fn estimated_subtree_size<T: Graph>(graph: &T) -> f64 {
+    let mut degrees = vec![0usize; POLY_SIZE];
+    for (u, v) in graph.edges() {
+        degrees[u] += 1;
+        degrees[v] += 1;
     }
+    degrees
+        .iter()
+        .map(|degree| ((*degree + 1) as f64).ln())
+        .sum::<f64>()
+        / 2.0
 }
 
 pub fn _calculate_matching_polynomial_binary<T: Graph>(graph: T) -> Polynomial<u64> {
@@ -1,8 +1,25 @@
 mod binary_representation;
 mod matching_poly;
+mod induced_subgraphs;
+mod enumeration;
+mod sampling;
+mod decomposition;
+mod modular;
 
 pub use binary_representation::BinaryGraph;
 pub use self::matching_poly::{ calculate_matching_polynomial_pointer,
     calculate_matching_polynomial_pointer_addresses,
     calculate_matching_polynomial_adaptive,
+    calculate_matching_polynomial_adaptive_with_config,
+    AdaptiveConfig, AdaptiveReport, SwitchRule,
     _calculate_matching_polynomial_binary};
+pub use self::induced_subgraphs::{InducedSubgraphTable,
+    calculate_matching_polynomial_memoised,
+    matching_polynomial_with_deck,
+    calculate_edge_deck_polynomials,
+    EdgeDeckPolynomials};
+pub use self::enumeration::{Matchings, matchings, matchings_of_size, perfect_matchings};
+pub use self::sampling::{sample_matching_of_size, sample_matching_of_size_with_table,
+    sample_monomer_dimer, sample_monomer_dimer_with_table};
+pub use self::decomposition::{DecompositionReport, calculate_matching_polynomial_decomposed, calculate_matching_polynomial_decomposed_with_report};
+pub use self::modular::{calculate_matching_polynomial_pointer_modular, calculate_matching_polynomial_decomposed_modular, calculate_matching_polynomial_crt, crt_primes, chinese_remainder};
@@ -0,0 +1,245 @@
+use crate::binary_graph_matching::BinaryGraph;
+use crate::binary_graph_matching::decomposition::{DecompositionReport, decomposed_polynomial};
+use crate::traits::Graph;
+use crate::polynomials::complete_matching_counts;
+use num::{BigUint, Zero, One, ToPrimitive};
+use std::mem::size_of;
+use std::thread;
+
+const POLY_SIZE: usize = size_of::<usize>()*8;
+
+/// The primes used by the CRT driver are taken downwards from here, so that
+/// sums of two residues stay well inside a u64.
+const PRIME_CEILING: u64 = 1 << 62;
+
+/// The modular variant of `calculate_matching_polynomial_pointer`: the same
+/// edge-deletion recursion, with every coefficient kept modulo `modulus`.
+///
+/// This engine adds one per leaf of the recursion, so its coefficients could
+/// only pass 2^64 after as many leaves, and the CRT driver does not use it.
+/// It is kept for the residues of the pointer engine itself, such as the
+/// parity of each coefficient with a modulus of 2.
+pub fn calculate_matching_polynomial_pointer_modular(graph: BinaryGraph, modulus: u64) -> [u64; POLY_SIZE] {
+    assert!(modulus > 1 && modulus <= PRIME_CEILING, "the modulus should lie in 2..=2^62");
+    let poly: &mut [u64; POLY_SIZE] = &mut [0; POLY_SIZE];
+    _calculate_matching_polynomial_static_modular(graph, poly, modulus);
+    *poly
+}
+
+fn _calculate_matching_polynomial_static_modular<T: Graph>(graph: T, poly: &mut [u64; POLY_SIZE], modulus: u64) {
+    if graph.edgeless() {
+        let node_count = graph.edgeless_node_count();
+        poly[node_count] += 1;
+        if poly[node_count] == modulus {
+            poly[node_count] = 0;
+        }
+    } else {
+        let (graph_prime, graph_prime_prime) = graph.get_graph_primes();
+        _calculate_matching_polynomial_static_modular(graph_prime_prime, poly, modulus);
+        _calculate_matching_polynomial_static_modular(graph_prime, poly, modulus);
+    }
+}
+
+/// The decomposing engine, `calculate_matching_polynomial_decomposed`, with
+/// every coefficient kept modulo `modulus`.
+///
+/// Unlike the pointer engine, the decomposing engine multiplies the
+/// polynomials of the parts it splits off, so on a graph made of many dense
+/// blocks it reaches coefficients far beyond a u64 in very little time. This
+/// is the engine the CRT driver runs.
+pub fn calculate_matching_polynomial_decomposed_modular(graph: BinaryGraph, modulus: u64) -> [u64; POLY_SIZE] {
+    assert!(modulus > 1 && modulus <= PRIME_CEILING, "the modulus should lie in 2..=2^62");
+    let mut report = DecompositionReport::default();
+    let coefficients = decomposed_polynomial(&graph.neighbourhoods(), graph.node_mask(), Some(modulus), &mut report);
+    let mut poly = [0; POLY_SIZE];
+    poly[..coefficients.len()].copy_from_slice(&coefficients);
+    poly
+}
+
+/// Calculates the matching polynomial exactly, for graphs whose coefficients
+/// overflow the u64 of the other engines, by running the modular decomposing
+/// engine for several primes at once, one thread each, and recombining the
+/// residues with the Chinese remainder theorem. The coefficients are returned
+/// indexed by the power of x.
+///
+/// No coefficient can exceed the number of matchings of the graph, which is
+/// at most 2^edges, and at most the number of matchings of the complete graph
+/// on as many nodes. We take primes until their product passes the smaller of
+/// the two.
+pub fn calculate_matching_polynomial_crt(graph: BinaryGraph) -> Vec<BigUint> {
+    let graph_size = graph.graph_size();
+    let bits = graph.edge_count().min(complete_matching_count(graph_size).bits() as usize) + 1;
+    let primes = crt_primes(bits);
+    let residues = thread::scope(|scope| {
+        let handles = primes
+            .iter()
+            .map(|prime| scope.spawn(move || calculate_matching_polynomial_decomposed_modular(graph, *prime)))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    (0..=graph_size)
+        .map(|i| {
+            let coefficient_residues = residues.iter().map(|poly| poly[i]).collect::<Vec<_>>();
+            chinese_remainder(&coefficient_residues, &primes)
+        })
+        .collect()
+}
+
+/// the number of matchings of K_n
+fn complete_matching_count(n: usize) -> BigUint {
+    complete_matching_counts::<BigUint>(n)[n].iter().sum()
+}
+
+/// The largest primes below 2^62, as many as are needed for their product to
+/// reach 2^bits.
+pub fn crt_primes(bits: usize) -> Vec<u64> {
+    let mut primes = Vec::new();
+    let mut product_bits = 0;
+    let mut candidate = PRIME_CEILING - 1;
+    while product_bits < bits.max(1) {
+        if is_prime(candidate) {
+            primes.push(candidate);
+            // each prime is above 2^61
+            product_bits += 61;
+        }
+        candidate -= 2;
+    }
+    primes
+}
+
+/// The unique x in 0..prod(moduli) with x = residues[i] mod moduli[i], for
+/// distinct prime moduli, built up one modulus at a time (Garner's method).
+pub fn chinese_remainder(residues: &[u64], moduli: &[u64]) -> BigUint {
+    assert_eq!(residues.len(), moduli.len(), "there should be one residue per modulus");
+    let mut x = BigUint::zero();
+    let mut product = BigUint::one();
+    for (residue, modulus) in residues.iter().zip(moduli.iter()) {
+        let x_mod = (&x % *modulus).to_u64().unwrap();
+        let product_mod = (&product % *modulus).to_u64().unwrap();
+        let difference = (residue % modulus + modulus - x_mod) % modulus;
+        let t = mul_mod(difference, pow_mod(product_mod, modulus - 2, *modulus), *modulus);
+        x += &product * t;
+        product *= *modulus;
+    }
+    x
+}
+
+fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    ((a as u128 * b as u128) % modulus as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    let mut result = 1 % modulus;
+    base %= modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, modulus);
+        }
+        base = mul_mod(base, base, modulus);
+        exponent >>= 1;
+    }
+    result
+}
+
+/// Miller-Rabin with the first twelve primes as witnesses, which is exact for
+/// every u64.
+fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+    if n < 2 {
+        return false
+    }
+    for p in WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p
+        }
+    }
+    let shift = (n - 1).trailing_zeros();
+    let d = (n - 1) >> shift;
+    'witness: for a in WITNESSES {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue
+        }
+        for _ in 1..shift {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness
+            }
+        }
+        return false
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_matching_polynomial_pointer;
+    use crate::graph_operations::graph_from_edges;
+
+    #[test]
+    fn test_modular_against_pointer() {
+        let mut data = [0; POLY_SIZE];
+        // the complete graph on ten nodes
+        for (node, row) in data.iter_mut().enumerate().take(10) {
+            *row = (1 << (10 - node)) - 1;
+        }
+        let graph = BinaryGraph::from(data);
+        let matching_poly = calculate_matching_polynomial_pointer(graph);
+        for modulus in [2, 7, 101, (1 << 61) - 1] {
+            let expected = matching_poly.map(|x| x % modulus);
+            assert_eq!(calculate_matching_polynomial_pointer_modular(graph, modulus), expected);
+            assert_eq!(calculate_matching_polynomial_decomposed_modular(graph, modulus), expected);
+        }
+        assert_eq!(complete_matching_count(10), BigUint::from(matching_poly.iter().sum::<u64>()));
+    }
+
+    #[test]
+    fn test_crt_beyond_u64() {
+        // six disjoint copies of K_10, so m(G) = m(K_10)^6, which has
+        // coefficients well past 2^64
+        let mut data = [0; POLY_SIZE];
+        for (node, row) in data.iter_mut().enumerate().take(60) {
+            let block_end = (node / 10 + 1) * 10;
+            *row = ((1 << (block_end - node)) - 1) << (60 - block_end);
+        }
+        let graph = BinaryGraph::from(data);
+        let block_poly = calculate_matching_polynomial_pointer(graph_from_edges(10, &(0..10)
+            .flat_map(|u| (u + 1..10).map(move |v| (u, v)))
+            .collect::<Vec<_>>()));
+        let block_poly = block_poly[..=10].iter().map(|x| BigUint::from(*x)).collect::<Vec<_>>();
+        let mut expected = vec![BigUint::one()];
+        for _ in 0..6 {
+            let mut product = vec![BigUint::zero(); expected.len() + 10];
+            for (i, x) in expected.iter().enumerate() {
+                for (j, y) in block_poly.iter().enumerate() {
+                    product[i + j] += x * y;
+                }
+            }
+            expected = product;
+        }
+        assert!(expected.iter().any(|x| x.bits() > 64));
+        assert_eq!(calculate_matching_polynomial_crt(graph), expected);
+    }
+
+    #[test]
+    fn test_chinese_remainder() {
+        assert_eq!(chinese_remainder(&[2, 3, 2], &[3, 5, 7]), BigUint::from(23u32));
+        let primes = crt_primes(200);
+        assert_eq!(primes.len(), 4);
+        assert!(primes.iter().all(|p| is_prime(*p) && *p < PRIME_CEILING));
+        assert!(!is_prime(PRIME_CEILING - 1));
+
+        // a number too big for any one prime comes back intact
+        let big = BigUint::from(3u32).pow(120);
+        let residues = primes
+            .iter()
+            .map(|p| (&big % *p).to_u64().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(chinese_remainder(&residues, &primes), big);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::binary_graph_matching::{BinaryGraph, InducedSubgraphTable};
+use crate::traits::Graph;
+use rand::Rng;
+
+/// Samples a matching with exactly `size` edges uniformly at random, or returns
+/// None if the graph has no such matching; see
+/// `sample_matching_of_size_with_table`.
+pub fn sample_matching_of_size<R: Rng + ?Sized>(graph: BinaryGraph, size: usize, rng: &mut R) -> Option<Vec<(usize, usize)>> {
+    sample_matching_of_size_with_table(&mut InducedSubgraphTable::new(&graph), size, rng)
+}
+
+/// Samples a matching with exactly `size` edges, uniformly at random, from
+/// the graph of the table, or returns None if it has no such matching.
+///
+/// We walk down the vertex recurrence from the whole graph. At an induced
+/// subgraph S that still needs j edges, with v its first node, the
+/// j-matchings of S split into those that leave v unmatched (the j-matchings
+/// of S - v) and, for each neighbour u of v, those that use the edge vu (the
+/// (j-1)-matchings of S - {u, v}). Taking each branch with probability its
+/// count over p_j(S) and repeating therefore picks each j-matching with equal
+/// probability. Every subgraph on the way is induced, so the counts come from
+/// the table, and the polynomials it builds for one sample are reused by the
+/// next; the whole walk costs no more than one run of the memoised engine.
+pub fn sample_matching_of_size_with_table<R: Rng + ?Sized>(table: &mut InducedSubgraphTable, size: usize, rng: &mut R) -> Option<Vec<(usize, usize)>> {
+    let mut nodes = table.node_mask();
+    let mut count = matchings_count(table, nodes, size);
+    if count == 0 {
+        return None
+    }
+
+    let mut matching = Vec::new();
+    while matching.len() < size {
+        let needed = size - matching.len();
+        let node = nodes.trailing_zeros() as usize;
+        let rest = nodes & !(1 << node);
+
+        // the branch whose range of counts the draw falls in
+        let mut draw = rng.gen_range(0..count);
+        let unmatched_count = matchings_count(table, rest, needed);
+        if draw < unmatched_count {
+            nodes = rest;
+            count = unmatched_count;
+            continue
+        }
+        draw -= unmatched_count;
+        let mut partners = table.neighbourhoods()[node] & rest;
+        loop {
+            let partner = partners.trailing_zeros() as usize;
+            partners &= partners - 1;
+            let sub_nodes = rest & !(1 << partner);
+            let sub_count = matchings_count(table, sub_nodes, needed - 1);
+            if draw < sub_count {
+                matching.push((node, partner));
+                nodes = sub_nodes;
+                count = sub_count;
+                break
+            }
+            draw -= sub_count;
+        }
+    }
+    Some(matching)
+}
+
+/// Samples a matching M from the monomer-dimer Gibbs distribution, in which
+/// M has probability proportional to activity^|M|; see
+/// `sample_monomer_dimer_with_table`.
+pub fn sample_monomer_dimer<R: Rng + ?Sized>(graph: BinaryGraph, activity: f64, rng: &mut R) -> Vec<(usize, usize)> {
+    sample_monomer_dimer_with_table(&mut InducedSubgraphTable::new(&graph), activity, rng)
+}
+
+/// Samples a matching of the graph of the table from the monomer-dimer
+/// distribution.
+///
+/// This is the same walk as in `sample_matching_of_size_with_table`, with
+/// the counts replaced by the partition functions Z(S) = sum_k p_k(S)
+/// activity^k. The first node v of S is left unmatched with probability
+/// Z(S - v) / Z(S), and matched to its neighbour u with probability
+/// activity * Z(S - {u, v}) / Z(S); the walk ends when no nodes are left.
+pub fn sample_monomer_dimer_with_table<R: Rng + ?Sized>(table: &mut InducedSubgraphTable, activity: f64, rng: &mut R) -> Vec<(usize, usize)> {
+    let mut nodes = table.node_mask();
+    let mut current_partition_function = partition_function(table, nodes, activity);
+    let mut matching = Vec::new();
+
+    while nodes != 0 {
+        let node = nodes.trailing_zeros() as usize;
+        let rest = nodes & !(1 << node);
+        let mut draw = rng.gen::<f64>() * current_partition_function;
+        let unmatched_partition_function = partition_function(table, rest, activity);
+
+        // if rounding leaves the draw past every branch, the last one is taken
+        let mut partner = None;
+        if draw >= unmatched_partition_function {
+            draw -= unmatched_partition_function;
+            let mut partners = table.neighbourhoods()[node] & rest;
+            while partners != 0 {
+                let candidate = partners.trailing_zeros() as usize;
+                partners &= partners - 1;
+                partner = Some(candidate);
+                let weight = activity * partition_function(table, rest & !(1 << candidate), activity);
+                if draw < weight {
+                    break
+                }
+                draw -= weight;
+            }
+        }
+        match partner {
+            Some(partner) => {
+                matching.push((node, partner));
+                nodes = rest & !(1 << partner);
+                current_partition_function = partition_function(table, nodes, activity);
+            },
+            None => {
+                nodes = rest;
+                current_partition_function = unmatched_partition_function;
+            },
+        }
+    }
+    matching
+}
+
+/// p_k(S), the number of k-matchings of the subgraph induced by `nodes`
+fn matchings_count(table: &mut InducedSubgraphTable, nodes: usize, k: usize) -> u64 {
+    let graph_size = nodes.count_ones() as usize;
+    match 2 * k <= graph_size {
+        true => table.matching_polynomial(nodes)[graph_size - 2 * k],
+        false => 0,
+    }
+}
+
+/// Z(S) = sum_k p_k(S) activity^k, for the subgraph induced by `nodes`
+fn partition_function(table: &mut InducedSubgraphTable, nodes: usize, activity: f64) -> f64 {
+    let graph_size = nodes.count_ones() as usize;
+    let matching_polynomial = table.matching_polynomial(nodes);
+    (0..=graph_size / 2)
+        .map(|k| matching_polynomial[graph_size - 2 * k] as f64 * activity.powi(k as i32))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_sample_matching_of_size() {
+        let fc_data = [
+            0b1111, 0b111, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0
+        ];
+        let graph = BinaryGraph::from(fc_data);
+        let mut rng = StdRng::seed_from_u64(1);
+
+        // the three perfect matchings of K_4 should come up about equally often
+        let mut table = InducedSubgraphTable::new(&graph);
+        let mut counts = HashMap::new();
+        for _ in 0..3000 {
+            let mut matching = sample_matching_of_size_with_table(&mut table, 2, &mut rng).unwrap();
+            matching.sort();
+            *counts.entry(matching).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 3);
+        assert!(counts.values().all(|count| (800..1200).contains(count)));
+        // the samples share the polynomials, of at most the 2^4 induced subgraphs
+        assert!(table.len() <= 16);
+        assert_eq!(sample_matching_of_size(graph, 1, &mut rng).unwrap().len(), 1);
+
+        assert_eq!(sample_matching_of_size(graph, 3, &mut rng), None);
+    }
+
+    #[test]
+    fn test_sample_monomer_dimer() {
+        // the path on three nodes: the empty matching has weight 1, and
+        // each of the two single edges has weight activity
+        let chain_data = [
+            0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0
+        ];
+        let graph = BinaryGraph::from(chain_data);
+        let mut rng = StdRng::seed_from_u64(2);
+        let samples = 4000;
+        let empty_count = (0..samples)
+            .filter(|_| sample_monomer_dimer(graph, 1.5, &mut rng).is_empty())
+            .count();
+        // P(empty) = 1 / (1 + 2 * 1.5) = 0.25
+        assert!((800..1200).contains(&empty_count));
+    }
+}
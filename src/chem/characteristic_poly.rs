@@ -0,0 +1,96 @@
+use crate::binary_graph_matching::BinaryGraph;
+
+/// The adjacency matrix of the graph, row-major, over the nodes still in it
+/// taken in order of label. Returns the matrix and its size.
+pub fn adjacency_matrix(graph: &BinaryGraph) -> (Vec<i64>, usize) {
+    let neighbourhoods = graph.neighbourhoods();
+    let node_mask = graph.node_mask();
+    let nodes = (0..graph.adjacency_width())
+        .filter(|i| node_mask & (1 << i) != 0)
+        .collect::<Vec<_>>();
+    let n = nodes.len();
+    let mut matrix = vec![0; n * n];
+    for (i, u) in nodes.iter().enumerate() {
+        for (j, v) in nodes.iter().enumerate() {
+            if neighbourhoods[*u] & (1 << v) != 0 {
+                matrix[i * n + j] = 1;
+            }
+        }
+    }
+    (matrix, n)
+}
+
+/// The characteristic polynomial det(xI - A) of the adjacency matrix,
+/// indexed by the power of x, by the Faddeev-LeVerrier algorithm:
+///     M_0 = 0, c_n = 1
+///     M_k = A M_(k-1) + c_(n-k+1) I,    c_(n-k) = -tr(A M_k) / k
+/// The divisions are exact, so this stays in the integers. The coefficients
+/// are i128, since those of dense graphs pass i64 well before 64 nodes.
+pub fn characteristic_polynomial(graph: &BinaryGraph) -> Vec<i128> {
+    let (adjacency, n) = adjacency_matrix(graph);
+    let adjacency = adjacency.iter().map(|x| *x as i128).collect::<Vec<_>>();
+    let mut coefficients = vec![0i128; n + 1];
+    coefficients[n] = 1;
+    let mut m = vec![0i128; n * n];
+    for k in 1..=n {
+        // M_k = A M_(k-1) + c_(n-k+1) I
+        let mut next = multiply(&adjacency, &m, n);
+        for i in 0..n {
+            next[i * n + i] += coefficients[n - k + 1];
+        }
+        m = next;
+        let product = multiply(&adjacency, &m, n);
+        let trace = (0..n).map(|i| product[i * n + i]).sum::<i128>();
+        coefficients[n - k] = -trace / k as i128;
+    }
+    coefficients
+}
+
+fn multiply(a: &[i128], b: &[i128], n: usize) -> Vec<i128> {
+    let mut product = vec![0; n * n];
+    for i in 0..n {
+        for k in 0..n {
+            if a[i * n + k] != 0 {
+                for j in 0..n {
+                    product[i * n + j] += a[i * n + k] * b[k * n + j];
+                }
+            }
+        }
+    }
+    product
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_operations::graph_from_edges;
+
+    #[test]
+    fn test_characteristic_polynomial() {
+        // C_6: x^6 - 6x^4 + 9x^2 - 4
+        let benzene = graph_from_edges(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+        assert_eq!(characteristic_polynomial(&benzene), vec![-4, 0, 9, 0, -6, 0, 1]);
+        // K_3 has eigenvalues 2, -1, -1: x^3 - 3x - 2
+        let triangle = graph_from_edges(3, &[(0, 1), (1, 2), (0, 2)]);
+        assert_eq!(characteristic_polynomial(&triangle), vec![-2, -3, 0, 1]);
+        // the star K_(1,3) is a tree, so it is its matching polynomial x^4 - 3x^2
+        let star = graph_from_edges(4, &[(0, 1), (0, 2), (0, 3)]);
+        assert_eq!(characteristic_polynomial(&star), vec![0, 0, -3, 0, 1]);
+        // K_64 is (x - 63)(x + 1)^63, whose coefficients pass i64
+        let complete_edges = (0..64).flat_map(|u| (u + 1..64).map(move |v| (u, v))).collect::<Vec<_>>();
+        let mut expected = vec![1i128];
+        for root in std::iter::once(63).chain(std::iter::repeat_n(-1, 63)) {
+            let mut next = vec![0; expected.len() + 1];
+            for (i, coefficient) in expected.iter().enumerate() {
+                next[i + 1] += coefficient;
+                next[i] -= root * coefficient;
+            }
+            expected = next;
+        }
+        assert!(expected.iter().any(|x| i64::try_from(*x).is_err()));
+        assert_eq!(characteristic_polynomial(&graph_from_edges(64, &complete_edges)), expected);
+        let (matrix, n) = adjacency_matrix(&star);
+        assert_eq!(n, 4);
+        assert_eq!(&matrix[..4], &[0, 1, 1, 1]);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::binary_graph_matching::{BinaryGraph, calculate_matching_polynomial_pointer};
+use crate::chem::{characteristic_polynomial, real_roots};
+use crate::polynomials::signed_matching_polynomial;
+use crate::traits::Graph;
+
+// Indices of molecular graphs, with the nodes the carbon atoms of a
+// conjugated system and the edges its bonds. Energies are in units of the
+// Huckel resonance integral beta.
+
+/// The Hosoya index Z(G), the number of matchings of the graph, including
+/// the empty one: the sum of the signless coefficients of m(G).
+pub fn hosoya_index(graph: &BinaryGraph) -> u64 {
+    calculate_matching_polynomial_pointer(*graph).iter().sum()
+}
+
+/// The matching energy, the sum of the absolute values of the roots of the
+/// matching polynomial.
+pub fn matching_energy(graph: &BinaryGraph) -> f64 {
+    let n = graph.graph_size();
+    let signed = signed_matching_polynomial::<f64>(&calculate_matching_polynomial_pointer(*graph), n);
+    real_roots(&signed).iter().map(|x| x.abs()).sum()
+}
+
+/// The energy of the graph, the sum of the absolute values of the
+/// eigenvalues of its adjacency matrix; for a closed-shell molecule this is
+/// its total pi-electron energy.
+pub fn graph_energy(graph: &BinaryGraph) -> f64 {
+    let coefficients = characteristic_polynomial(graph)
+        .iter()
+        .map(|x| *x as f64)
+        .collect::<Vec<_>>();
+    real_roots(&coefficients).iter().map(|x| x.abs()).sum()
+}
+
+/// The topological resonance energy of Aihara and Gutman et al., the
+/// pi-electron energy less that of the acyclic reference structure, whose
+/// characteristic polynomial is the matching polynomial:
+///     TRE(G) = E(G) - ME(G)
+/// It is 0 for trees, positive for aromatic systems and negative for
+/// antiaromatic ones.
+pub fn topological_resonance_energy(graph: &BinaryGraph) -> f64 {
+    graph_energy(graph) - matching_energy(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_operations::graph_from_edges;
+
+    #[test]
+    fn test_chemical_indices() {
+        let benzene = graph_from_edges(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+        assert_eq!(hosoya_index(&benzene), 18);
+        assert!((graph_energy(&benzene) - 8.0).abs() < 1e-6);
+        // the roots of m(C_6) are 2 cos((2k + 1) pi / 12)
+        let expected = (0..6)
+            .map(|k| (2.0 * ((2 * k + 1) as f64 * std::f64::consts::PI / 12.0).cos()).abs())
+            .sum::<f64>();
+        assert!((matching_energy(&benzene) - expected).abs() < 1e-9);
+        assert!((topological_resonance_energy(&benzene) - 0.2726).abs() < 1e-4);
+
+        let naphthalene = graph_from_edges(10, &[
+            (0, 1), (1, 2), (2, 3), (3, 4), (4, 9), (9, 0),
+            (4, 5), (5, 6), (6, 7), (7, 8), (8, 9),
+        ]);
+        assert_eq!(hosoya_index(&naphthalene), 148);
+        assert!(topological_resonance_energy(&naphthalene) > 0.0);
+
+        // cyclobutadiene is antiaromatic, with eigenvalues 2, 0, 0 and -2
+        let cyclobutadiene = graph_from_edges(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert!((graph_energy(&cyclobutadiene) - 4.0).abs() < 1e-6);
+        assert!(topological_resonance_energy(&cyclobutadiene) < 0.0);
+
+        // butadiene is a path, which has no resonance energy
+        let butadiene = graph_from_edges(4, &[(0, 1), (1, 2), (2, 3)]);
+        assert_eq!(hosoya_index(&butadiene), 5);
+        assert!(topological_resonance_energy(&butadiene).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,9 @@
+mod characteristic_poly;
+mod roots;
+mod indices;
+mod smiles;
+
+pub use self::characteristic_poly::{adjacency_matrix, characteristic_polynomial};
+pub use self::roots::real_roots;
+pub use self::indices::{hosoya_index, matching_energy, graph_energy, topological_resonance_energy};
+pub use self::smiles::{parse_smiles, Molecule, SmilesError};
@@ -0,0 +1,88 @@
+use num::complex::Complex64;
+use num::Zero;
+
+/// The roots of a polynomial whose roots are all real, as are those of the
+/// matching and characteristic polynomials, in increasing order. The
+/// coefficients are indexed by the power of x. Roots at 0 are split off
+/// exactly; the rest are found together by the Aberth-Ehrlich iteration,
+/// which handles the repeated roots of symmetric graphs, if more slowly.
+pub fn real_roots(coefficients: &[f64]) -> Vec<f64> {
+    let mut coefficients = coefficients.to_vec();
+    while coefficients.last().is_some_and(|x| *x == 0.0) {
+        coefficients.pop();
+    }
+    let zero_roots = coefficients.iter().take_while(|x| **x == 0.0).count();
+    let reduced = &coefficients[zero_roots..];
+
+    let mut roots = vec![0.0; zero_roots];
+    roots.extend(aberth(reduced).iter().map(|z| z.re));
+    roots.sort_by(|x, y| x.total_cmp(y));
+    roots
+}
+
+fn aberth(coefficients: &[f64]) -> Vec<Complex64> {
+    let degree = coefficients.len().saturating_sub(1);
+    if degree == 0 {
+        return Vec::new()
+    }
+    let lead = coefficients[degree];
+    let monic = coefficients.iter().map(|x| x / lead).collect::<Vec<_>>();
+
+    // start on a circle enclosing every root, off the real axis
+    let radius = 1.0 + monic[..degree].iter().map(|x| x.abs()).fold(0.0, f64::max);
+    let mut roots = (0..degree)
+        .map(|k| Complex64::from_polar(radius, (2.0 * std::f64::consts::PI * k as f64 + 0.4) / degree as f64))
+        .collect::<Vec<_>>();
+    for _ in 0..10000 {
+        let mut largest_step: f64 = 0.0;
+        for i in 0..degree {
+            let (value, derivative) = evaluate(&monic, roots[i]);
+            if value.is_zero() {
+                continue
+            }
+            let ratio = value / derivative;
+            let repulsion = (0..degree)
+                .filter(|j| *j != i)
+                .map(|j| (roots[i] - roots[j]).inv())
+                .sum::<Complex64>();
+            let step = ratio / (1.0 - ratio * repulsion);
+            roots[i] -= step;
+            largest_step = largest_step.max(step.norm() / roots[i].norm().max(1.0));
+        }
+        if largest_step < 1e-15 {
+            break
+        }
+    }
+    roots
+}
+
+/// p(z) and p'(z) by Horner's rule
+fn evaluate(coefficients: &[f64], z: Complex64) -> (Complex64, Complex64) {
+    let mut value = Complex64::zero();
+    let mut derivative = Complex64::zero();
+    for coefficient in coefficients.iter().rev() {
+        derivative = derivative * z + value;
+        value = value * z + coefficient;
+    }
+    (value, derivative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_real_roots() {
+        // (x - 1)(x + 2)(x - 3) x^2 = x^5 - 2x^4 - 5x^3 + 6x^2
+        let roots = real_roots(&[0.0, 0.0, 6.0, -5.0, -2.0, 1.0]);
+        for (root, expected) in roots.iter().zip([-2.0, 0.0, 0.0, 1.0, 3.0]) {
+            assert!((root - expected).abs() < 1e-10);
+        }
+        // x^3 - 3x - 2 = (x - 2)(x + 1)^2, with a double root
+        let roots = real_roots(&[-2.0, -3.0, 0.0, 1.0]);
+        for (root, expected) in roots.iter().zip([-1.0, -1.0, 2.0]) {
+            assert!((root - expected).abs() < 1e-6);
+        }
+        assert!(real_roots(&[3.0]).is_empty());
+    }
+}
@@ -0,0 +1,409 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::mem::size_of;
+use crate::binary_graph_matching::BinaryGraph;
+use crate::graph_operations::graph_from_edges;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+
+// A small SMILES reader, enough for hydrocarbons and heterocycles: atoms of
+// the organic subset, aromatic lowercase atoms, simple bracket atoms such as
+// [nH] or [O-], bonds, branches, ring closures (including %nn) and '.'
+// between components. Hydrogens are suppressed, so only the heavy-atom
+// skeleton goes into the graph, and bond orders are dropped, since the
+// matching polynomial only sees the skeleton. Chirality, wildcard atoms,
+// explicit hydrogen atoms such as [H] and anything else outside this subset
+// give an error rather than a wrong graph.
+
+/// A molecule read from SMILES: the hydrogen-suppressed skeleton, with nodes
+/// numbered in the order their atoms appear, and a side table of the atoms
+/// which are not carbon.
+#[derive(Debug, Clone)]
+pub struct Molecule {
+    pub graph: BinaryGraph,
+    pub node_count: usize,
+    /// the element symbol of each atom which is not carbon, capitalised
+    pub heteroatoms: BTreeMap<usize, String>,
+    /// whether each atom was written as aromatic
+    pub aromatic: Vec<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SmilesError {
+    UnexpectedCharacter { position: usize, character: char },
+    UnsupportedConstruct { position: usize, construct: String },
+    UnknownElement { position: usize, symbol: String },
+    UnclosedBracket { position: usize },
+    UnmatchedBranchClose { position: usize },
+    UnclosedBranch { position: usize },
+    BranchWithoutAtom { position: usize },
+    DanglingBond { position: usize },
+    UnclosedRing { label: u32 },
+    InvalidRingBond { position: usize, label: u32 },
+    TooManyAtoms,
+}
+
+impl fmt::Display for SmilesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SmilesError::UnexpectedCharacter { position, character } =>
+                write!(f, "unexpected character '{}' at position {}", character, position),
+            SmilesError::UnsupportedConstruct { position, construct } =>
+                write!(f, "{} at position {} is not supported", construct, position),
+            SmilesError::UnknownElement { position, symbol } =>
+                write!(f, "unknown element '{}' at position {}", symbol, position),
+            SmilesError::UnclosedBracket { position } =>
+                write!(f, "the bracket atom at position {} is not closed", position),
+            SmilesError::UnmatchedBranchClose { position } =>
+                write!(f, "the ')' at position {} closes no branch", position),
+            SmilesError::UnclosedBranch { position } =>
+                write!(f, "the branch opened at position {} is not closed", position),
+            SmilesError::BranchWithoutAtom { position } =>
+                write!(f, "the branch at position {} does not follow an atom", position),
+            SmilesError::DanglingBond { position } =>
+                write!(f, "the bond at position {} does not join two atoms", position),
+            SmilesError::UnclosedRing { label } =>
+                write!(f, "ring closure {} is opened but never closed", label),
+            SmilesError::InvalidRingBond { position, label } =>
+                write!(f, "ring closure {} at position {} joins an atom to itself or repeats a bond", label, position),
+            SmilesError::TooManyAtoms =>
+                write!(f, "the molecule has more than {} heavy atoms", MAX_NODES),
+        }
+    }
+}
+
+impl std::error::Error for SmilesError {}
+
+/// the elements allowed in bracket atoms
+const ELEMENTS: [&str; 36] = [
+    "H", "He", "Li", "Be", "B", "C", "N", "O", "F", "Ne", "Na", "Mg", "Al", "Si", "P", "S", "Cl", "Ar",
+    "K", "Ca", "Ti", "Cr", "Mn", "Fe", "Co", "Ni", "Cu", "Zn", "Ga", "Ge", "As", "Se", "Br", "Sn", "Te", "I",
+];
+
+/// the aromatic atoms, written in lowercase
+const AROMATIC: [&str; 8] = ["b", "c", "n", "o", "p", "s", "se", "as"];
+
+struct Parser {
+    characters: Vec<char>,
+    position: usize,
+    edges: Vec<(usize, usize)>,
+    heteroatoms: BTreeMap<usize, String>,
+    aromatic: Vec<bool>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.characters.get(self.position).copied()
+    }
+
+    fn add_atom(&mut self, symbol: &str, aromatic: bool) -> Result<usize, SmilesError> {
+        let node = self.aromatic.len();
+        if node == MAX_NODES {
+            return Err(SmilesError::TooManyAtoms)
+        }
+        let mut element = symbol.to_string();
+        if aromatic {
+            element[..1].make_ascii_uppercase();
+        }
+        if element != "C" {
+            self.heteroatoms.insert(node, element);
+        }
+        self.aromatic.push(aromatic);
+        Ok(node)
+    }
+
+    fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.edges.contains(&(u.min(v), u.max(v)))
+    }
+
+    fn add_edge(&mut self, u: usize, v: usize) {
+        self.edges.push((u.min(v), u.max(v)));
+    }
+
+    /// an atom of the organic subset, written without brackets
+    fn organic_atom(&mut self) -> Result<usize, SmilesError> {
+        let start = self.position;
+        let character = self.peek().unwrap();
+        let next = self.characters.get(start + 1).copied();
+        let (symbol, aromatic) = match (character, next) {
+            ('C', Some('l')) => ("Cl", false),
+            ('B', Some('r')) => ("Br", false),
+            ('B', _) => ("B", false),
+            ('C', _) => ("C", false),
+            ('N', _) => ("N", false),
+            ('O', _) => ("O", false),
+            ('P', _) => ("P", false),
+            ('S', _) => ("S", false),
+            ('F', _) => ("F", false),
+            ('I', _) => ("I", false),
+            ('b', _) => ("b", true),
+            ('c', _) => ("c", true),
+            ('n', _) => ("n", true),
+            ('o', _) => ("o", true),
+            ('p', _) => ("p", true),
+            ('s', _) => ("s", true),
+            _ => {
+                // the rest of the elements must be written in brackets
+                let symbol = self.characters[start..]
+                    .iter()
+                    .take(2)
+                    .enumerate()
+                    .take_while(|(i, c)| *i == 0 || c.is_ascii_lowercase())
+                    .map(|(_, c)| *c)
+                    .collect::<String>();
+                return Err(SmilesError::UnknownElement { position: start, symbol })
+            }
+        };
+        self.position += symbol.len();
+        self.add_atom(symbol, aromatic)
+    }
+
+    /// a bracket atom: an optional isotope, the element, then optional
+    /// hydrogen count, charge and atom class, which are all read and dropped
+    fn bracket_atom(&mut self) -> Result<usize, SmilesError> {
+        let start = self.position;
+        self.position += 1;
+        let end = self.characters[start..]
+            .iter()
+            .position(|c| *c == ']')
+            .map(|offset| start + offset)
+            .ok_or(SmilesError::UnclosedBracket { position: start })?;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+
+        // the longest element symbol that matches, aromatic or not
+        let symbol_start = self.position;
+        let candidate = |length: usize| self.characters[symbol_start..end.min(symbol_start + length)].iter().collect::<String>();
+        let (symbol, aromatic) = [2, 1]
+            .iter()
+            .map(|length| candidate(*length))
+            .find_map(|symbol| match (ELEMENTS.contains(&symbol.as_str()), AROMATIC.contains(&symbol.as_str())) {
+                (true, _) => Some((symbol, false)),
+                (_, true) => Some((symbol, true)),
+                _ => None,
+            })
+            .ok_or_else(|| match self.characters.get(symbol_start) {
+                Some('*') => SmilesError::UnsupportedConstruct { position: symbol_start, construct: "a wildcard atom".to_string() },
+                _ => SmilesError::UnknownElement { position: symbol_start, symbol: candidate(2) },
+            })?;
+        // an explicit hydrogen would become a node of the skeleton
+        if symbol == "H" {
+            return Err(SmilesError::UnsupportedConstruct { position: symbol_start, construct: "an explicit hydrogen atom".to_string() })
+        }
+        self.position += symbol.len();
+
+        while self.position < end {
+            let character = self.peek().unwrap();
+            match character {
+                '@' => return Err(SmilesError::UnsupportedConstruct { position: self.position, construct: "chirality".to_string() }),
+                'H' | '+' | '-' | ':' | '0'..='9' => self.position += 1,
+                _ => return Err(SmilesError::UnexpectedCharacter { position: self.position, character }),
+            }
+        }
+        self.position = end + 1;
+        self.add_atom(&symbol, aromatic)
+    }
+
+    /// a ring closure label, a digit or % and two digits
+    fn ring_label(&mut self) -> Result<u32, SmilesError> {
+        let character = self.peek().unwrap();
+        if character != '%' {
+            self.position += 1;
+            return Ok(character.to_digit(10).unwrap())
+        }
+        let digits = self.characters[self.position + 1..]
+            .iter()
+            .take(2)
+            .collect::<String>();
+        match digits.len() == 2 && digits.chars().all(|c| c.is_ascii_digit()) {
+            true => {
+                self.position += 3;
+                Ok(digits.parse().unwrap())
+            },
+            false => Err(SmilesError::UnexpectedCharacter { position: self.position, character }),
+        }
+    }
+}
+
+/// Reads a SMILES string into the hydrogen-suppressed molecular graph.
+pub fn parse_smiles(smiles: &str) -> Result<Molecule, SmilesError> {
+    let mut parser = Parser {
+        characters: smiles.chars().collect(),
+        position: 0,
+        edges: Vec::new(),
+        heteroatoms: BTreeMap::new(),
+        aromatic: Vec::new(),
+    };
+    // the atom the next one bonds to, whether a ring closure may follow (it
+    // must come straight after its atom, or a bond after it), the atoms at
+    // which open branches started, the bond waiting for its second atom, and
+    // the open rings
+    let mut previous: Option<usize> = None;
+    let mut ring_allowed = false;
+    let mut branches: Vec<(usize, usize)> = Vec::new();
+    let mut pending_bond: Option<usize> = None;
+    let mut rings: HashMap<u32, (usize, usize)> = HashMap::new();
+
+    while let Some(character) = parser.peek() {
+        let position = parser.position;
+        match character {
+            '[' | 'A'..='Z' | 'a'..='z' => {
+                let atom = match character {
+                    '[' => parser.bracket_atom()?,
+                    _ => parser.organic_atom()?,
+                };
+                if let Some(previous) = previous {
+                    parser.add_edge(previous, atom);
+                }
+                previous = Some(atom);
+                ring_allowed = true;
+                pending_bond = None;
+            },
+            '-' | '=' | '#' | '$' | ':' | '/' | '\\' => {
+                if previous.is_none() || pending_bond.is_some() {
+                    return Err(SmilesError::DanglingBond { position })
+                }
+                pending_bond = Some(position);
+                parser.position += 1;
+            },
+            '(' => {
+                match previous {
+                    Some(atom) if pending_bond.is_none() => branches.push((atom, position)),
+                    _ => return Err(SmilesError::BranchWithoutAtom { position }),
+                }
+                ring_allowed = false;
+                parser.position += 1;
+            },
+            ')' => {
+                if let Some(bond) = pending_bond {
+                    return Err(SmilesError::DanglingBond { position: bond })
+                }
+                let (atom, _) = branches.pop().ok_or(SmilesError::UnmatchedBranchClose { position })?;
+                previous = Some(atom);
+                ring_allowed = false;
+                parser.position += 1;
+            },
+            '0'..='9' | '%' => {
+                let atom = match previous {
+                    Some(atom) if ring_allowed => atom,
+                    _ => return Err(SmilesError::UnexpectedCharacter { position, character }),
+                };
+                let label = parser.ring_label()?;
+                match rings.remove(&label) {
+                    None => {
+                        rings.insert(label, (atom, position));
+                    },
+                    Some((opening, _)) => {
+                        if opening == atom || parser.has_edge(opening, atom) {
+                            return Err(SmilesError::InvalidRingBond { position, label })
+                        }
+                        parser.add_edge(opening, atom);
+                    },
+                }
+                pending_bond = None;
+            },
+            '.' => {
+                if let Some(bond) = pending_bond {
+                    return Err(SmilesError::DanglingBond { position: bond })
+                }
+                previous = None;
+                ring_allowed = false;
+                parser.position += 1;
+            },
+            '*' => return Err(SmilesError::UnsupportedConstruct { position, construct: "a wildcard atom".to_string() }),
+            _ => return Err(SmilesError::UnexpectedCharacter { position, character }),
+        }
+    }
+
+    if let Some(bond) = pending_bond {
+        return Err(SmilesError::DanglingBond { position: bond })
+    }
+    if let Some((_, position)) = branches.pop() {
+        return Err(SmilesError::UnclosedBranch { position })
+    }
+    if let Some(label) = rings.keys().min() {
+        return Err(SmilesError::UnclosedRing { label: *label })
+    }
+    let node_count = parser.aromatic.len();
+    Ok(Molecule {
+        graph: graph_from_edges(node_count, &parser.edges),
+        node_count,
+        heteroatoms: parser.heteroatoms,
+        aromatic: parser.aromatic,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_graph_matching::calculate_matching_polynomial_pointer;
+    use crate::chem::hosoya_index;
+    use crate::traits::Graph;
+
+    #[test]
+    fn test_parse_smiles() {
+        // benzene, written both ways, is C_6
+        for smiles in ["c1ccccc1", "C1=CC=CC=C1"] {
+            let benzene = parse_smiles(smiles).unwrap();
+            assert_eq!(benzene.node_count, 6);
+            assert!(benzene.heteroatoms.is_empty());
+            assert_eq!(&calculate_matching_polynomial_pointer(benzene.graph)[..7], &[2, 0, 9, 0, 6, 0, 1]);
+        }
+        assert!(parse_smiles("c1ccccc1").unwrap().aromatic.iter().all(|x| *x));
+
+        let naphthalene = parse_smiles("c1ccc2ccccc2c1").unwrap();
+        assert_eq!(hosoya_index(&naphthalene.graph), 148);
+        assert_eq!(parse_smiles("c1ccc%12ccccc%12c1").unwrap().graph.edges(), naphthalene.graph.edges());
+
+        // isobutane is the star K_(1,3)
+        let isobutane = parse_smiles("CC(C)C").unwrap();
+        assert_eq!(isobutane.graph.edges(), vec![(0, 1), (1, 2), (1, 3)]);
+
+        let pyridine = parse_smiles("c1ccncc1").unwrap();
+        assert_eq!(pyridine.heteroatoms, BTreeMap::from([(3, "N".to_string())]));
+        let pyrrole = parse_smiles("[nH]1cccc1").unwrap();
+        assert_eq!(pyrrole.heteroatoms, BTreeMap::from([(0, "N".to_string())]));
+        assert_eq!(pyrrole.graph.edge_count(), 5);
+        let chloroethanol = parse_smiles("ClCCO").unwrap();
+        assert_eq!(chloroethanol.heteroatoms, BTreeMap::from([(0, "Cl".to_string()), (3, "O".to_string())]));
+        let acetate = parse_smiles("CC(=O)[O-].[Na+]").unwrap();
+        assert_eq!(acetate.node_count, 5);
+        assert_eq!(acetate.graph.edges(), vec![(0, 1), (1, 2), (1, 3)]);
+        assert_eq!(acetate.heteroatoms.get(&4), Some(&"Na".to_string()));
+        assert_eq!(parse_smiles("").unwrap().node_count, 0);
+
+        assert_eq!(parse_smiles("C1CC").unwrap_err(), SmilesError::UnclosedRing { label: 1 });
+        assert_eq!(parse_smiles("CC)").unwrap_err(), SmilesError::UnmatchedBranchClose { position: 2 });
+        assert_eq!(parse_smiles("C(C").unwrap_err(), SmilesError::UnclosedBranch { position: 1 });
+        assert_eq!(parse_smiles("(C)C").unwrap_err(), SmilesError::BranchWithoutAtom { position: 0 });
+        assert_eq!(parse_smiles("CC=").unwrap_err(), SmilesError::DanglingBond { position: 2 });
+        // a ring closure belongs to the atom (or bond) just before it
+        assert_eq!(parse_smiles("C=1CCCC1").unwrap().graph.edges(), parse_smiles("C1CCCC1").unwrap().graph.edges());
+        assert_eq!(parse_smiles("C1CC(C)1").unwrap_err(), SmilesError::UnexpectedCharacter { position: 7, character: '1' });
+        assert_eq!(parse_smiles("C1CC(C)=1").unwrap_err(), SmilesError::UnexpectedCharacter { position: 8, character: '1' });
+        assert_eq!(parse_smiles("C11").unwrap_err(), SmilesError::InvalidRingBond { position: 2, label: 1 });
+        assert_eq!(parse_smiles("C[C@H](O)N").unwrap_err(), SmilesError::UnsupportedConstruct {
+            position: 3,
+            construct: "chirality".to_string(),
+        });
+        // explicit hydrogens are not folded into the skeleton
+        for smiles in ["[H]C([H])([H])[H]", "C[2H]"] {
+            assert!(matches!(parse_smiles(smiles).unwrap_err(), SmilesError::UnsupportedConstruct { .. }));
+        }
+        assert_eq!(parse_smiles("[H]C").unwrap_err(), SmilesError::UnsupportedConstruct {
+            position: 1,
+            construct: "an explicit hydrogen atom".to_string(),
+        });
+        assert_eq!(parse_smiles("[He]").unwrap().heteroatoms, BTreeMap::from([(0, "He".to_string())]));
+        assert_eq!(parse_smiles("C*").unwrap_err(), SmilesError::UnsupportedConstruct {
+            position: 1,
+            construct: "a wildcard atom".to_string(),
+        });
+        assert_eq!(parse_smiles("CNa").unwrap_err(), SmilesError::UnknownElement { position: 2, symbol: "a".to_string() });
+        assert_eq!(parse_smiles("C[Xx]").unwrap_err(), SmilesError::UnknownElement { position: 2, symbol: "Xx".to_string() });
+        assert_eq!(parse_smiles("C[C").unwrap_err(), SmilesError::UnclosedBracket { position: 1 });
+        assert_eq!(parse_smiles(&"C".repeat(65)).unwrap_err(), SmilesError::TooManyAtoms);
+        assert_eq!(SmilesError::UnclosedRing { label: 3 }.to_string(), "ring closure 3 is opened but never closed");
+    }
+}
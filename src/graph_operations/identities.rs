@@ -0,0 +1,139 @@
+use crate::polynomials::{complement_polynomial, complement_polynomial_inverse};
+use num::{BigUint, Zero};
+use std::mem::size_of;
+
+const POLY_SIZE: usize = size_of::<usize>()*8;
+
+// The functions here derive the matching polynomial of the result of a graph
+// operation from those of its operands, without building the new graph. All
+// polynomials are signless and indexed by the power of x, as the engines
+// return them, and a graph on n nodes has n + 1 coefficients. The
+// Cartesian product has no such identity, and has to be computed afresh.
+
+/// m(G u H) = m(G) m(H), as a matching of the union is one of each side.
+pub fn disjoint_union_polynomial(g_poly: &[u64], g_size: usize, h_poly: &[u64], h_size: usize) -> [u64; POLY_SIZE] {
+    assert!(g_size + h_size < POLY_SIZE, "the union has more than {} nodes", POLY_SIZE - 1);
+    let mut poly = [0; POLY_SIZE];
+    for (i, g_coefficient) in g_poly.iter().enumerate().take(g_size + 1) {
+        for (j, h_coefficient) in h_poly.iter().enumerate().take(h_size + 1) {
+            poly[i + j] += g_coefficient * h_coefficient;
+        }
+    }
+    poly
+}
+
+/// The complement of the join G + H is the disjoint union of the
+/// complements, so m(G + H) is the inverse complement transform of the
+/// product of the complements' polynomials.
+pub fn join_polynomial(g_poly: &[u64], g_size: usize, h_poly: &[u64], h_size: usize) -> Vec<BigUint> {
+    let g_complement = complement_polynomial(g_poly, g_size);
+    let h_complement = complement_polynomial(h_poly, h_size);
+    let mut union_complement = vec![BigUint::zero(); g_size + h_size + 1];
+    for (i, g_coefficient) in g_complement.iter().enumerate() {
+        for (j, h_coefficient) in h_complement.iter().enumerate() {
+            union_complement[i + j] += g_coefficient * h_coefficient;
+        }
+    }
+    complement_polynomial_inverse(&union_complement, g_size + h_size)
+}
+
+/// Adding a pendant node w at u, the vertex recurrence at w gives
+///     m(G + w) = x m(G) - m(G - u)
+/// which in signless coefficients is x m(G) + m(G - u).
+pub fn pendant_polynomial(g_poly: &[u64], g_minus_u_poly: &[u64], g_size: usize) -> [u64; POLY_SIZE] {
+    assert!(g_size + 1 < POLY_SIZE, "the new graph has more than {} nodes", POLY_SIZE - 1);
+    let mut poly = [0; POLY_SIZE];
+    for i in 0..=g_size {
+        poly[i + 1] += g_poly[i];
+    }
+    for i in 0..g_size {
+        poly[i] += g_minus_u_poly[i];
+    }
+    poly
+}
+
+/// Subdividing the edge uv with a new node w, the vertex recurrence at w
+/// gives
+///     m(G_uv) = x m(G - uv) - m(G - u) - m(G - v)
+/// where G - uv has lost the edge and G - u, G - v a node.
+pub fn subdivision_polynomial(g_minus_uv_poly: &[u64], g_minus_u_poly: &[u64], g_minus_v_poly: &[u64], g_size: usize) -> [u64; POLY_SIZE] {
+    assert!(g_size + 1 < POLY_SIZE, "the new graph has more than {} nodes", POLY_SIZE - 1);
+    let mut poly = [0; POLY_SIZE];
+    for i in 0..=g_size {
+        poly[i + 1] += g_minus_uv_poly[i];
+    }
+    for i in 0..g_size {
+        poly[i] += g_minus_u_poly[i] + g_minus_v_poly[i];
+    }
+    poly
+}
+
+/// Identifying u in G with v in H, the shared node is unmatched, matched in
+/// G or matched in H, which gives
+///     m(G.H) = m(G) m(H - v) + m(G - u) m(H) - x m(G - u) m(H - v)
+/// and the same with signless coefficients.
+pub fn coalescence_polynomial(g_poly: &[u64], g_minus_u_poly: &[u64], g_size: usize,
+                              h_poly: &[u64], h_minus_v_poly: &[u64], h_size: usize) -> [u64; POLY_SIZE] {
+    assert!(g_size >= 1 && h_size >= 1, "the graphs should each have the node to identify");
+    let first = disjoint_union_polynomial(g_poly, g_size, h_minus_v_poly, h_size - 1);
+    let second = disjoint_union_polynomial(g_minus_u_poly, g_size - 1, h_poly, h_size);
+    let third = disjoint_union_polynomial(g_minus_u_poly, g_size - 1, h_minus_v_poly, h_size - 1);
+    let mut poly = [0; POLY_SIZE];
+    for i in 0..g_size + h_size {
+        // the sum is a count of matchings, so wrapping leaves it exact
+        poly[i] = first[i].wrapping_add(second[i]);
+        if i >= 1 {
+            poly[i] = poly[i].wrapping_sub(third[i - 1]);
+        }
+    }
+    poly
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_graph_matching::{BinaryGraph, calculate_matching_polynomial_pointer};
+    use crate::graph_operations::*;
+    use crate::traits::Graph;
+
+    fn without_node(graph: &BinaryGraph, node: usize) -> BinaryGraph {
+        let mut graph = *graph;
+        graph.remove_node(node, graph.adjacency_width());
+        graph
+    }
+
+    #[test]
+    fn test_identities() {
+        let g = graph_from_edges(5, &[(0, 1), (0, 2), (1, 2), (2, 3), (3, 4), (1, 4)]);
+        let h = graph_from_edges(4, &[(0, 1), (1, 2), (2, 3), (0, 3), (0, 2)]);
+        let g_poly = calculate_matching_polynomial_pointer(g);
+        let h_poly = calculate_matching_polynomial_pointer(h);
+
+        let union_poly = calculate_matching_polynomial_pointer(disjoint_union(&g, &h));
+        assert_eq!(disjoint_union_polynomial(&g_poly, 5, &h_poly, 4), union_poly);
+
+        let join_poly = calculate_matching_polynomial_pointer(join(&g, &h));
+        let expected = join_poly[..=9]
+            .iter()
+            .map(|x| BigUint::from(*x))
+            .collect::<Vec<_>>();
+        assert_eq!(join_polynomial(&g_poly, 5, &h_poly, 4), expected);
+
+        let g_minus_u_poly = calculate_matching_polynomial_pointer(without_node(&g, 3));
+        let pendant_poly = calculate_matching_polynomial_pointer(add_pendant(&g, 3));
+        assert_eq!(pendant_polynomial(&g_poly, &g_minus_u_poly, 5), pendant_poly);
+
+        let h_minus_v_poly = calculate_matching_polynomial_pointer(without_node(&h, 0));
+        let coalescence_poly = calculate_matching_polynomial_pointer(coalescence(&g, 3, &h, 0));
+        assert_eq!(coalescence_polynomial(&g_poly, &g_minus_u_poly, 5, &h_poly, &h_minus_v_poly, 4), coalescence_poly);
+
+        let mut g_minus_uv = g;
+        g_minus_uv.remove_edge(1, 4, 5);
+        let subdivision_poly = calculate_matching_polynomial_pointer(subdivide_edge(&g, 1, 4));
+        assert_eq!(subdivision_polynomial(&calculate_matching_polynomial_pointer(g_minus_uv),
+                                          &calculate_matching_polynomial_pointer(without_node(&g, 1)),
+                                          &calculate_matching_polynomial_pointer(without_node(&g, 4)),
+                                          5),
+                   subdivision_poly);
+    }
+}
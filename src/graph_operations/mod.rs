@@ -0,0 +1,5 @@
+mod operations;
+mod identities;
+
+pub use self::operations::{graph_from_edges, disjoint_union, join, coalescence, subdivide_edge, add_pendant, cartesian_product};
+pub use self::identities::{disjoint_union_polynomial, join_polynomial, pendant_polynomial, subdivision_polynomial, coalescence_polynomial};
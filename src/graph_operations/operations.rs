@@ -0,0 +1,121 @@
+use crate::binary_graph_matching::BinaryGraph;
+use crate::traits::Graph;
+use std::mem::size_of;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+
+/// Builds the graph on nodes 0..node_count with the given edges, in the
+/// layout of `BinaryGraph::from`.
+pub fn graph_from_edges(node_count: usize, edges: &[(usize, usize)]) -> BinaryGraph {
+    assert!(node_count <= MAX_NODES, "the graph holds at most {} nodes", MAX_NODES);
+    let mut data = [0; MAX_NODES];
+    for (node, row) in data.iter_mut().enumerate().take(node_count) {
+        *row = 1 << (node_count - node - 1);
+    }
+    for (u, v) in edges {
+        let (u, v) = if u < v { (*u, *v) } else { (*v, *u) };
+        if u != v {
+            data[u] |= 1 << (node_count - v - 1);
+        }
+    }
+    BinaryGraph::from(data)
+}
+
+/// The disjoint union of G and H, with the nodes of H placed after those of G.
+pub fn disjoint_union(g: &BinaryGraph, h: &BinaryGraph) -> BinaryGraph {
+    let (g_size, h_size) = (g.adjacency_width(), h.adjacency_width());
+    let mut edges = g.edges();
+    edges.extend(h.edges().into_iter().map(|(u, v)| (u + g_size, v + g_size)));
+    graph_from_edges(g_size + h_size, &edges)
+}
+
+/// The join of G and H: their disjoint union, with every node of G joined to
+/// every node of H.
+pub fn join(g: &BinaryGraph, h: &BinaryGraph) -> BinaryGraph {
+    let (g_size, h_size) = (g.adjacency_width(), h.adjacency_width());
+    let mut edges = disjoint_union(g, h).edges();
+    for u in 0..g_size {
+        edges.extend((0..h_size).map(|v| (u, g_size + v)));
+    }
+    graph_from_edges(g_size + h_size, &edges)
+}
+
+/// The coalescence of G and H, identifying node u of G with node v of H. The
+/// nodes of G keep their labels, and the other nodes of H follow them in
+/// order.
+pub fn coalescence(g: &BinaryGraph, u: usize, h: &BinaryGraph, v: usize) -> BinaryGraph {
+    let (g_size, h_size) = (g.adjacency_width(), h.adjacency_width());
+    assert!(u < g_size && v < h_size, "the nodes to identify should be in their graphs");
+    let relabel = |node: usize| match node {
+        node if node == v => u,
+        node if node < v => g_size + node,
+        node => g_size + node - 1,
+    };
+    let mut edges = g.edges();
+    edges.extend(h.edges().into_iter().map(|(a, b)| (relabel(a), relabel(b))));
+    graph_from_edges(g_size + h_size - 1, &edges)
+}
+
+/// Subdivides the edge uv of G, replacing it with a path u - w - v through a
+/// new node w, which is labelled last.
+pub fn subdivide_edge(g: &BinaryGraph, u: usize, v: usize) -> BinaryGraph {
+    let g_size = g.adjacency_width();
+    let (u, v) = if u < v { (u, v) } else { (v, u) };
+    let mut edges = g.edges();
+    let position = edges
+        .iter()
+        .position(|edge| *edge == (u, v))
+        .expect("the edge to subdivide should be in the graph");
+    edges.remove(position);
+    edges.extend([(u, g_size), (v, g_size)]);
+    graph_from_edges(g_size + 1, &edges)
+}
+
+/// Adds a pendant node, labelled last, joined only to node u of G.
+pub fn add_pendant(g: &BinaryGraph, u: usize) -> BinaryGraph {
+    let g_size = g.adjacency_width();
+    assert!(u < g_size, "the node to attach to should be in the graph");
+    let mut edges = g.edges();
+    edges.push((u, g_size));
+    graph_from_edges(g_size + 1, &edges)
+}
+
+/// The Cartesian product of G and H, with node (a, b) labelled
+/// a * |H| + b. Two nodes are joined when they agree in one coordinate and
+/// are joined in the other.
+pub fn cartesian_product(g: &BinaryGraph, h: &BinaryGraph) -> BinaryGraph {
+    let (g_size, h_size) = (g.adjacency_width(), h.adjacency_width());
+    let mut edges = Vec::new();
+    for a in 0..g_size {
+        edges.extend(h.edges().into_iter().map(|(b, c)| (a * h_size + b, a * h_size + c)));
+    }
+    for (a, c) in g.edges() {
+        edges.extend((0..h_size).map(|b| (a * h_size + b, c * h_size + b)));
+    }
+    graph_from_edges(g_size * h_size, &edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transfer_matrix::{Boundary, strip_graph};
+
+    #[test]
+    fn test_operations() {
+        let path = graph_from_edges(3, &[(0, 1), (1, 2)]);
+        let edge = graph_from_edges(2, &[(0, 1)]);
+
+        let union = disjoint_union(&path, &edge);
+        assert_eq!(union.edges(), vec![(0, 1), (1, 2), (3, 4)]);
+        assert_eq!(join(&path, &edge).edge_count(), 2 + 1 + 6);
+
+        // two paths joined end to end make a longer path
+        let long_path = coalescence(&path, 2, &path, 0);
+        assert_eq!(long_path, graph_from_edges(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]));
+        assert_eq!(subdivide_edge(&edge, 0, 1), graph_from_edges(3, &[(0, 2), (1, 2)]));
+        assert_eq!(add_pendant(&edge, 1), path);
+
+        // P_3 x P_2 is the 2 x 3 ladder
+        assert_eq!(cartesian_product(&path, &edge), strip_graph(2, 3, Boundary::Free));
+    }
+}
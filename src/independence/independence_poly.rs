@@ -0,0 +1,81 @@
+use crate::binary_graph_matching::BinaryGraph;
+use std::mem::size_of;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+// one longer than the matching polynomials, as a set may take every node
+const POLY_SIZE: usize = size_of::<usize>()*8 + 1;
+
+/// Calculates the independence polynomial I(G, x) = sum_k i_k x^k, where i_k
+/// is the number of independent sets of k nodes, with the coefficients
+/// indexed by k. This works for any graph; for the claw-free ones, line
+/// graphs among them, all its roots are real.
+///
+/// The recursion is the node analogue of edge deletion: for any node v,
+///     I(G) = I(G - v) + x I(G - N[v])
+/// according to whether v is left out of the set or put in it, in which case
+/// its neighbours must be left out. As in the pointer engine, nothing is
+/// built on the way back up: we carry the number of nodes already chosen and
+/// add the contribution of each leaf straight into the polynomial. We branch
+/// on a node of highest degree, and a leaf is reached once no edges are
+/// left, when r isolated nodes contribute (1 + x)^r.
+pub fn independence_polynomial(graph: BinaryGraph) -> [u64; POLY_SIZE] {
+    let neighbourhoods = graph.neighbourhoods();
+    let binomials = binomial_table(MAX_NODES);
+    let poly: &mut [u64; POLY_SIZE] = &mut [0; POLY_SIZE];
+    _independence_polynomial(&neighbourhoods, graph.node_mask(), 0, &binomials, poly);
+    *poly
+}
+
+fn _independence_polynomial(neighbourhoods: &[usize; MAX_NODES], nodes: usize, chosen: usize,
+                            binomials: &[Vec<u64>], poly: &mut [u64; POLY_SIZE]) {
+    let branch_node = (0..MAX_NODES)
+        .filter(|node| nodes & (1 << node) != 0)
+        .max_by_key(|node| (neighbourhoods[*node] & nodes).count_ones());
+    match branch_node {
+        Some(node) if neighbourhoods[node] & nodes != 0 => {
+            _independence_polynomial(neighbourhoods, nodes & !(1 << node), chosen, binomials, poly);
+            let closed_neighbourhood = neighbourhoods[node] | (1 << node);
+            _independence_polynomial(neighbourhoods, nodes & !closed_neighbourhood, chosen + 1, binomials, poly);
+        }
+        _ => {
+            let isolated = nodes.count_ones() as usize;
+            for (k, binomial) in binomials[isolated].iter().enumerate() {
+                poly[chosen + k] += binomial;
+            }
+        }
+    }
+}
+
+/// row r holds the binomial coefficients C(r, 0), ..., C(r, r)
+fn binomial_table(size: usize) -> Vec<Vec<u64>> {
+    let mut table: Vec<Vec<u64>> = vec![vec![1]];
+    for r in 1..=size {
+        let row = (0..=r)
+            .map(|k| {
+                let left = if k > 0 { table[r - 1][k - 1] } else { 0 };
+                let right = table[r - 1].get(k).copied().unwrap_or(0);
+                left + right
+            })
+            .collect();
+        table.push(row);
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_operations::graph_from_edges;
+
+    #[test]
+    fn test_independence_polynomial() {
+        let path = graph_from_edges(4, &[(0, 1), (1, 2), (2, 3)]);
+        assert_eq!(independence_polynomial(path)[..4], [1, 4, 3, 0]);
+        let cycle = graph_from_edges(5, &[(0, 1), (1, 2), (2, 3), (3, 4), (0, 4)]);
+        assert_eq!(independence_polynomial(cycle)[..4], [1, 5, 5, 0]);
+        // the empty graph on 64 nodes reaches the last coefficient
+        let edgeless = graph_from_edges(64, &[]);
+        let poly = independence_polynomial(edgeless);
+        assert_eq!((poly[0], poly[1], poly[32], poly[64]), (1, 64, 1832624140942590534, 1));
+    }
+}
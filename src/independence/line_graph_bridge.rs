@@ -0,0 +1,86 @@
+use crate::binary_graph_matching::{BinaryGraph, calculate_matching_polynomial_pointer};
+use crate::independence::independence_polynomial;
+use crate::traits::Graph;
+use std::mem::size_of;
+
+const POLY_SIZE: usize = size_of::<usize>()*8;
+
+// A matching of G is an independent set of its line graph, so the number of
+// k-matchings of G is i_k(L(G)), and
+//     m(G, x) = x^n I(L(G), -x^(-2))
+// for G on n nodes. With signless coefficients the sign goes, and the
+// conversions below just move the coefficient of x^k in I to that of
+// x^(n - 2k) in m and back.
+
+/// The signless matching polynomial of a graph on n nodes, indexed by the
+/// power of x, from the independence polynomial of its line graph.
+pub fn independence_to_matching_polynomial(independence_poly: &[u64], n: usize) -> [u64; POLY_SIZE] {
+    let mut matching_poly = [0; POLY_SIZE];
+    for (k, coefficient) in independence_poly.iter().enumerate().take(n / 2 + 1) {
+        matching_poly[n - 2 * k] = *coefficient;
+    }
+    matching_poly
+}
+
+/// The independence polynomial of the line graph of a graph on n nodes,
+/// indexed by k, from its signless matching polynomial.
+pub fn matching_to_independence_polynomial(matching_poly: &[u64], n: usize) -> Vec<u64> {
+    (0..=n / 2)
+        .map(|k| matching_poly[n - 2 * k])
+        .collect()
+}
+
+/// Calculates the matching polynomial of G through the independence
+/// polynomial of L(G), which branches on edges of G by the edges they meet.
+pub fn matching_polynomial_from_line_graph(graph: BinaryGraph) -> [u64; POLY_SIZE] {
+    let independence_poly = independence_polynomial(graph.line_graph());
+    independence_to_matching_polynomial(&independence_poly, graph.graph_size())
+}
+
+/// Checks m(G) from the pointer engine against I(L(G)).
+pub fn check_line_graph_identity(graph: BinaryGraph) -> bool {
+    let graph_size = graph.graph_size();
+    let matching_poly = calculate_matching_polynomial_pointer(graph);
+    let independence_poly = independence_polynomial(graph.line_graph());
+    let converted = matching_to_independence_polynomial(&matching_poly, graph_size);
+    independence_poly[..converted.len()] == converted[..]
+        && independence_poly[converted.len()..].iter().all(|x| *x == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_operations::graph_from_edges;
+
+    #[test]
+    fn test_line_graph() {
+        let triangle = graph_from_edges(3, &[(0, 1), (1, 2), (0, 2)]);
+        let claw = graph_from_edges(4, &[(0, 1), (0, 2), (0, 3)]);
+        assert_eq!(claw.line_graph(), triangle);
+        assert_eq!(triangle.line_graph(), triangle);
+        let path = graph_from_edges(4, &[(0, 1), (1, 2), (2, 3)]);
+        assert_eq!(path.line_graph(), graph_from_edges(3, &[(0, 1), (1, 2)]));
+    }
+
+    #[test]
+    fn test_line_graph_identity() {
+        let standard_data = [
+            0b11001, 0b1001, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        let complete = graph_from_edges(7, &(0..7).flat_map(|u| (u + 1..7).map(move |v| (u, v))).collect::<Vec<_>>());
+        let petersen = graph_from_edges(10, &[
+            (0, 1), (1, 2), (2, 3), (3, 4), (0, 4),
+            (0, 5), (1, 6), (2, 7), (3, 8), (4, 9),
+            (5, 7), (7, 9), (6, 9), (6, 8), (5, 8),
+        ]);
+        for graph in [BinaryGraph::from(standard_data), complete, petersen] {
+            assert!(check_line_graph_identity(graph));
+            assert_eq!(matching_polynomial_from_line_graph(graph), calculate_matching_polynomial_pointer(graph));
+        }
+        // the Petersen graph has six perfect matchings
+        assert_eq!(matching_polynomial_from_line_graph(petersen)[0], 6);
+    }
+}
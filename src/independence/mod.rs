@@ -0,0 +1,6 @@
+mod independence_poly;
+mod line_graph_bridge;
+
+pub use self::independence_poly::independence_polynomial;
+pub use self::line_graph_bridge::{independence_to_matching_polynomial, matching_to_independence_polynomial,
+    matching_polynomial_from_line_graph, check_line_graph_identity};
@@ -5,6 +5,16 @@ pub mod weighted_graph_matching;
 pub mod petgraph;
 pub mod binary_graph_matching;
 pub mod polynomials;
+pub mod reconstruction;
+pub mod maximum_matching;
+pub mod planar;
+pub mod matrix_functions;
+pub mod transfer_matrix;
+pub mod tree_decomposition;
+pub mod graph_operations;
+pub mod independence;
+pub mod statistics;
+pub mod chem;
 
 use traits::Graph;
 use polynomials::{poly2herme,  hermadd, hermemulx , herme2poly};
@@ -533,6 +543,28 @@ mod tests {
         assert_eq!(matching_polynomial[..graph.graph_size()+1], [0, 15, 0, -10, 0, 1]);
     }
 
+    #[test]
+    fn matching_polynomial_adaptive_complete() {
+        // the complete graph on 20 nodes, whose matching polynomial is He_20;
+        // its coefficients are beyond the 24 bits of an f32 mantissa
+        let mut fc_data = [0; 64];
+        for (node, row) in fc_data.iter_mut().enumerate().take(20) {
+            *row = (1 << (20 - node)) - 1;
+        }
+        let graph = BinaryGraph::from(fc_data);
+        let matching_polynomial = calculate_matching_polynomial_adaptive(graph);
+
+        // (-1)^k 20! / (2^k k! (20 - 2k)!) at x^(20 - 2k)
+        let mut expected = [0i64; 21];
+        expected[20] = 1;
+        for k in 1..=10 {
+            let ratio = ((22 - 2 * k) * (21 - 2 * k)) as i64;
+            expected[20 - 2 * k] = -expected[22 - 2 * k] * ratio / (2 * k) as i64;
+        }
+        assert_eq!(matching_polynomial[..21], expected);
+        assert_eq!(matching_polynomial[0], 654729075);
+    }
+
     #[test]
     fn matching_polynomial_adaptive_2() {
         // grpah size is 4
@@ -637,6 +669,83 @@ mod tests {
     }
 
 
+    #[test]
+    fn matching_polynomial_adaptive_config() {
+        use binary_graph_matching::{AdaptiveConfig, SwitchRule, calculate_matching_polynomial_adaptive_with_config};
+
+        let configs = [
+            AdaptiveConfig::default(),
+            AdaptiveConfig { density_threshold: 0.3, allow_switch_back: true, rule: SwitchRule::Density },
+            AdaptiveConfig { density_threshold: 0.7, allow_switch_back: true, rule: SwitchRule::Density },
+            AdaptiveConfig { density_threshold: 0.5, allow_switch_back: true, rule: SwitchRule::EstimatedSubtreeSize },
+        ];
+        for pattern in [(7, 3, 5, 3), (1, 1, 4, 1), (2, 5, 7, 5)] {
+            // a graph on nine nodes, joining i < j when (a i + b j) % c < d
+            let (a, b, c, d) = pattern;
+            let mut data = [0; 64];
+            for i in 0..9 {
+                data[i] = 1 << (8 - i);
+                for j in i + 1..9 {
+                    if (a * i + b * j) % c < d {
+                        data[i] |= 1 << (8 - j);
+                    }
+                }
+            }
+            let graph = BinaryGraph::from(data);
+            let pointer_poly = calculate_matching_polynomial_pointer(graph);
+            let expected = (0..=9)
+                .map(|i| if ((9 - i) / 2) % 2 == 0 { pointer_poly[i] as i64 } else { -(pointer_poly[i] as i64) })
+                .collect::<Vec<_>>();
+            for config in configs {
+                let (matching_polynomial, report) = calculate_matching_polynomial_adaptive_with_config(graph, config);
+                assert_eq!(matching_polynomial[..10], expected[..], "{:?} {:?}", pattern, config);
+                assert!(report.paths() > 0);
+                if !config.allow_switch_back {
+                    assert!(report.max_switches() <= 1);
+                }
+            }
+        }
+
+        // the complete graph switches once, straight to an edgeless complement
+        let fc_data = [
+            0b11111, 0b1111, 0b111, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0
+        ];
+        let (_, report) = calculate_matching_polynomial_adaptive_with_config(BinaryGraph::from(fc_data), AdaptiveConfig::default());
+        assert_eq!(report.switch_histogram, vec![0, 1]);
+        // a path only switches near the leaves, where a single edge is dense
+        let chain_data = [
+            0b110000, 0b11000, 0b1100, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+        ];
+        let (_, report) = calculate_matching_polynomial_adaptive_with_config(BinaryGraph::from(chain_data), AdaptiveConfig::default());
+        assert_eq!(report.switch_histogram, vec![8, 5]);
+
+        // the complement of an edge 0-9 together with K_8 less an 8-cycle on
+        // the nodes between: the engine switches to that complement, and once
+        // the edge 0-9 is taken, what is left is dense enough to switch back
+        let mut data = [0; 64];
+        for i in 0..10 {
+            data[i] = 1 << (9 - i);
+            for j in i + 1..10 {
+                let on_cycle = j == i + 1 || (i == 1 && j == 8);
+                if (i == 0 && j == 9) || (i > 0 && j < 9 && !on_cycle) {
+                    data[i] |= 1 << (9 - j);
+                }
+            }
+        }
+        let graph = BinaryGraph::from(data).complement();
+        let config = AdaptiveConfig { allow_switch_back: true, ..AdaptiveConfig::default() };
+        let (matching_polynomial, report) = calculate_matching_polynomial_adaptive_with_config(graph, config);
+        let (expected, default_report) = calculate_matching_polynomial_adaptive_with_config(graph, AdaptiveConfig::default());
+        assert_eq!(matching_polynomial, expected);
+        assert_eq!(default_report.max_switches(), 1);
+        assert!(report.max_switches() >= 2);
+    }
+
     #[test]
     fn test_density() {
         let fc_data = [
@@ -75,6 +75,7 @@ pub trait GraphProperties {
     fn remove_node(&mut self, node: usize, graph_size : usize);
     fn remove_edge(&mut self, node1: usize, node2: usize, graph_size: usize);
     fn edgeless_node_count(&self) -> usize;
+    fn edges(&self) -> Vec<(usize, usize)>;
 }
 impl GraphProperties for GraphData {
     fn edgeless(&self) -> bool {
@@ -130,6 +131,20 @@ impl GraphProperties for GraphData {
             .count_ones() as usize
     }
 
+    /// lists the edges as (node1, node2) pairs with node1 < node2
+    fn edges(&self) -> Vec<(usize, usize)> {
+        let width = encoding_width(self);
+        let mut edges = Vec::new();
+        for (i, row) in self.iter().enumerate().filter(|(_, row)| **row != 0) {
+            for j in (i + 1)..width {
+                if row & (1 << (width - j - 1)) != 0 {
+                    edges.push((i, j));
+                }
+            }
+        }
+        edges
+    }
+
     fn get_relevant_edge(&self) -> (usize, usize, usize) {
         // since the nodes are ordered in INCREASING order of degree, we can
         // just drop the last(right-most in the binary representation)
@@ -190,6 +205,18 @@ impl GraphProperties for GraphData {
     }
 }
 
+/// The number of nodes the graph was encoded with, which graph_size undercounts
+/// once nodes have been removed. Each remaining row still has its diagonal bit
+/// as its leading one, as in get_relevant_edge, so the width comes from the
+/// first of them.
+fn encoding_width(graph: &GraphData) -> usize {
+    graph
+        .iter()
+        .enumerate()
+        .find(|(_, row)| **row != 0)
+        .map_or(0, |(i, row)| MAX_NODES - (row.leading_zeros() as usize - i))
+}
+
 pub fn get_deck(graph: &GraphData) -> Vec<GraphData> {
     let mut deck: Vec<GraphData> = Vec::new();
     let graph_size = graph.graph_size();
@@ -200,3 +227,36 @@ pub fn get_deck(graph: &GraphData) -> Vec<GraphData> {
     }
     deck
 }
+
+pub fn get_edge_deck(graph: &GraphData) -> Vec<GraphData> {
+    let mut deck: Vec<GraphData> = Vec::new();
+    let graph_size = encoding_width(graph);
+    for (node1, node2) in graph.edges() {
+        let mut current_graph = *graph;
+        current_graph.remove_edge(node1, node2, graph_size);
+        deck.push(current_graph);
+    }
+    deck
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edges_of_deck_card() {
+        // the path 0 - 1 - 2, and the card with node 0 removed, which keeps
+        // the edge (1, 2)
+        let mut path: GraphData = [0; POLY_SIZE];
+        path[..3].copy_from_slice(&[0b110, 0b11, 0b1]);
+        let card = get_deck(&path)[0];
+        assert_eq!(&card[..3], &[0, 0b11, 0b1]);
+        assert_eq!(card.edges(), vec![(1, 2)]);
+
+        let edge_deck = get_edge_deck(&card);
+        assert_eq!(edge_deck.len(), 1);
+        assert!(edge_deck[0].edgeless());
+        assert_eq!(edge_deck[0].edgeless_node_count(), 2);
+        assert_eq!(path.edges(), vec![(0, 1), (1, 2)]);
+    }
+}
@@ -1,3 +1,3 @@
 mod matching_poly;
 
-pub use matching_poly::{calculate_matching_polynomial_raw, GraphProperties, GraphData, get_deck};
+pub use matching_poly::{calculate_matching_polynomial_raw, GraphProperties, GraphData, get_deck, get_edge_deck};
@@ -0,0 +1,178 @@
+use crate::binary_graph_matching::BinaryGraph;
+use crate::weighted_graph_matching::WeightedGraph;
+use num::complex::Complex64;
+use num::{Zero, One};
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::ops::Mul;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+
+/// The hafnian of a symmetric n x n matrix A, stored row-major:
+///     haf(A) = sum over perfect matchings M of K_n of prod_{ij in M} a_ij
+/// i.e. the constant term of the weighted matching polynomial of K_n with
+/// w(ij) = a_ij. Only the entries above the diagonal are read. The hafnian of
+/// a matrix of odd size is 0.
+pub fn hafnian(matrix: &[f64], n: usize) -> f64 {
+    HafnianTable::new(matrix, n, false).value()
+}
+
+/// The hafnian of a complex symmetric matrix, as in `hafnian`.
+pub fn hafnian_complex(matrix: &[Complex64], n: usize) -> Complex64 {
+    HafnianTable::new(matrix, n, false).value()
+}
+
+/// The loop hafnian of a symmetric n x n matrix A, in which the diagonal
+/// entries act as loops:
+///     lhaf(A) = sum over matchings M of K_n of
+///               prod_{ij in M} a_ij prod_{i not covered by M} a_ii
+pub fn loop_hafnian(matrix: &[f64], n: usize) -> f64 {
+    HafnianTable::new(matrix, n, true).value()
+}
+
+/// The loop hafnian of a complex symmetric matrix, as in `loop_hafnian`.
+pub fn loop_hafnian_complex(matrix: &[Complex64], n: usize) -> Complex64 {
+    HafnianTable::new(matrix, n, true).value()
+}
+
+/// Builds the weighted complete graph K_n with w(ij) = a_ij from a symmetric
+/// matrix, so that its weighted matching polynomial has the hafnian as its
+/// constant term.
+pub fn weighted_graph_from_matrix(matrix: &[f64], n: usize) -> WeightedGraph {
+    assert!(n <= MAX_NODES, "the weighted graph holds at most {} nodes", MAX_NODES);
+    let mut data = [0; MAX_NODES];
+    let mut weights = [0.0; MAX_NODES*MAX_NODES];
+    for i in 0..n {
+        // the cap bit and an edge to every later node, n - i bits in all
+        data[i] = usize::MAX >> (MAX_NODES - (n - i));
+        for j in (i + 1)..n {
+            weights[i * n + j] = matrix[i * n + j] as f32;
+        }
+    }
+    WeightedGraph {
+        graph: BinaryGraph::from(data),
+        weights,
+    }
+}
+
+/// A memo table over the subsets of the rows still to be paired off. With S
+/// the remaining rows and i the first of them,
+///     haf(S) = sum_{j in S - i} a_ij haf(S - i - j)
+/// and for the loop hafnian the term a_ii lhaf(S - i) is added, for the case
+/// where i is left unpaired. Only subsets reachable in this way are stored.
+struct HafnianTable<'a, T> {
+    matrix: &'a [T],
+    n: usize,
+    loops: bool,
+    values: HashMap<usize, T>,
+}
+
+impl<'a, T: Copy + Zero + One + Mul<Output = T>> HafnianTable<'a, T> {
+    fn new(matrix: &'a [T], n: usize, loops: bool) -> HafnianTable<'a, T> {
+        assert_eq!(matrix.len(), n * n, "the matrix should have n * n entries");
+        assert!(n <= MAX_NODES, "the rows are kept in a mask of {} bits", MAX_NODES);
+        HafnianTable {
+            matrix,
+            n,
+            loops,
+            values: HashMap::new(),
+        }
+    }
+
+    fn value(&mut self) -> T {
+        let rows = if self.n == MAX_NODES { usize::MAX } else { (1 << self.n) - 1 };
+        self.subset_value(rows)
+    }
+
+    fn subset_value(&mut self, rows: usize) -> T {
+        if rows == 0 {
+            return T::one()
+        }
+        if !self.loops && rows.count_ones() % 2 == 1 {
+            return T::zero()
+        }
+        if let Some(value) = self.values.get(&rows) {
+            return *value
+        }
+
+        let i = rows.trailing_zeros() as usize;
+        let rest = rows & !(1 << i);
+        let mut value = T::zero();
+        if self.loops {
+            value = value + self.matrix[i * self.n + i] * self.subset_value(rest);
+        }
+        let mut partners = rest;
+        while partners != 0 {
+            let j = partners.trailing_zeros() as usize;
+            partners &= partners - 1;
+            let weight = self.matrix[i * self.n + j];
+            if !weight.is_zero() {
+                value = value + weight * self.subset_value(rest & !(1 << j));
+            }
+        }
+        self.values.insert(rows, value);
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weighted_graph_matching::_calculate_weighted_matching_polynomial_binary;
+
+    #[test]
+    fn test_hafnian() {
+        // the hafnian of the all-ones matrix counts the perfect matchings of K_n
+        assert_eq!(hafnian(&[1.0; 36], 6), 15.0);
+        assert_eq!(hafnian(&[1.0; 25], 5), 0.0);
+        assert_eq!(hafnian(&[], 0), 1.0);
+        // and the loop hafnian counts all of the matchings
+        assert_eq!(loop_hafnian(&[1.0; 16], 4), 10.0);
+
+        // haf of a 4 x 4 matrix is a01 a23 + a02 a13 + a03 a12
+        let matrix = [
+            0.0, 1.0, 2.0, 3.0,
+            1.0, 0.0, 4.0, 5.0,
+            2.0, 4.0, 0.0, 6.0,
+            3.0, 5.0, 6.0, 0.0,
+        ];
+        assert_eq!(hafnian(&matrix, 4), 6.0 + 10.0 + 12.0);
+
+        let complex_matrix = matrix.map(|x| Complex64::new(x, -x));
+        // each term is a product of two entries (1 - i) a_ij, so picks up (1 - i)^2 = -2i
+        assert_eq!(hafnian_complex(&complex_matrix, 4), Complex64::new(0.0, -56.0));
+    }
+
+    #[test]
+    fn test_hafnian_against_weighted_polynomial() {
+        let n = 6;
+        let mut matrix = vec![0.0; n * n];
+        for i in 0..n {
+            for j in 0..n {
+                matrix[i * n + j] = ((i + j) % 4) as f64 * 0.5 + (i * j) as f64 * 0.25;
+            }
+        }
+        let weighted_graph = weighted_graph_from_matrix(&matrix, n);
+        let weighted_poly = _calculate_weighted_matching_polynomial_binary(weighted_graph);
+        let constant_term = weighted_poly.data().first().copied().unwrap_or(0.0);
+        assert!((hafnian(&matrix, n) - constant_term as f64).abs() < 1e-3);
+
+        // a full 64 x 64 matrix fills every bit of the first row
+        let weighted_graph = weighted_graph_from_matrix(&vec![1.0; MAX_NODES * MAX_NODES], MAX_NODES);
+        assert_eq!(weighted_graph.graph.data()[0], usize::MAX);
+        assert_eq!(weighted_graph.graph.data()[MAX_NODES - 1], 1);
+    }
+
+    #[test]
+    fn test_loop_hafnian() {
+        // lhaf = a00 a11 a22 + a00 a12 + a11 a02 + a22 a01 for n = 3
+        let matrix = [
+            2.0, 1.0, 3.0,
+            1.0, 5.0, 4.0,
+            3.0, 4.0, 7.0,
+        ];
+        assert_eq!(loop_hafnian(&matrix, 3), 70.0 + 8.0 + 15.0 + 7.0);
+        let complex_matrix = matrix.map(|x| Complex64::new(x, 0.0));
+        assert_eq!(loop_hafnian_complex(&complex_matrix, 3), Complex64::new(100.0, 0.0));
+    }
+}
@@ -0,0 +1,5 @@
+mod hafnian;
+mod permanent;
+
+pub use self::hafnian::{hafnian, hafnian_complex, loop_hafnian, loop_hafnian_complex, weighted_graph_from_matrix};
+pub use self::permanent::{bipartite_graph_from_board, bipartite_weighted_graph_from_matrix, rook_polynomial, permanent_01, permanent, permanent_ryser, permanent_glynn};
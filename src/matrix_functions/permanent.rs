@@ -0,0 +1,198 @@
+use crate::binary_graph_matching::{BinaryGraph, calculate_matching_polynomial_pointer};
+use crate::weighted_graph_matching::{WeightedGraph, _calculate_weighted_matching_polynomial_binary};
+use std::mem::size_of;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+
+/// Turns an m x n board (or 0/1 matrix), stored row-major with non-zero
+/// entries marking the cells, into its bipartite graph: nodes 0..m are the
+/// rows, nodes m..m + n the columns, and row i is joined to column j when
+/// cell (i, j) is on the board.
+pub fn bipartite_graph_from_board(board: &[u8], m: usize, n: usize) -> BinaryGraph {
+    assert_eq!(board.len(), m * n, "the board should have m * n entries");
+    assert!(m + n <= MAX_NODES, "the bipartite graph holds at most {} nodes", MAX_NODES);
+    let graph_size = m + n;
+    let mut data = [0; MAX_NODES];
+    for (node, row) in data.iter_mut().enumerate().take(graph_size) {
+        *row = 1 << (graph_size - node - 1);
+    }
+    for i in 0..m {
+        for j in 0..n {
+            if board[i * n + j] != 0 {
+                data[i] |= 1 << (n - j - 1);
+            }
+        }
+    }
+    BinaryGraph::from(data)
+}
+
+/// The weighted version of `bipartite_graph_from_board`: row i is joined to
+/// column j when a_ij is non-zero, with weight a_ij.
+pub fn bipartite_weighted_graph_from_matrix(matrix: &[f64], m: usize, n: usize) -> WeightedGraph {
+    let board = matrix
+        .iter()
+        .map(|x| (*x != 0.0) as u8)
+        .collect::<Vec<_>>();
+    let graph = bipartite_graph_from_board(&board, m, n);
+    let graph_size = m + n;
+    let mut weights = [0.0; MAX_NODES*MAX_NODES];
+    for i in 0..m {
+        for j in 0..n {
+            weights[i * graph_size + m + j] = matrix[i * n + j] as f32;
+        }
+    }
+    WeightedGraph {
+        graph,
+        weights,
+    }
+}
+
+/// The rook polynomial R(B, x) = sum_k r_k x^k of an m x n board, where r_k
+/// is the number of ways of placing k non-attacking rooks. These placements
+/// are the k-matchings of the bipartite graph of the board, so r_k is the
+/// coefficient of x^(m + n - 2k) in its matching polynomial. The coefficients
+/// are returned in increasing powers of x.
+pub fn rook_polynomial(board: &[u8], m: usize, n: usize) -> Vec<u64> {
+    let graph_size = m + n;
+    let matching_polynomial = calculate_matching_polynomial_pointer(bipartite_graph_from_board(board, m, n));
+    (0..=m.min(n))
+        .map(|k| matching_polynomial[graph_size - 2 * k])
+        .collect()
+}
+
+/// The permanent of an n x n 0/1 matrix, i.e. the number of perfect
+/// matchings of its bipartite graph.
+pub fn permanent_01(board: &[u8], n: usize) -> u64 {
+    rook_polynomial(board, n, n)[n]
+}
+
+/// The permanent of a real n x n matrix, read off as the constant term of the
+/// weighted matching polynomial of its bipartite graph. The weighted engine
+/// works in f32 and recurses once per edge, so beyond small matrices
+/// `permanent_ryser` or `permanent_glynn` are to be preferred.
+pub fn permanent(matrix: &[f64], n: usize) -> f64 {
+    if n == 0 {
+        return 1.0
+    }
+    let weighted_graph = bipartite_weighted_graph_from_matrix(matrix, n, n);
+    let weighted_poly = _calculate_weighted_matching_polynomial_binary(weighted_graph);
+    weighted_poly.data().first().copied().unwrap_or(0.0) as f64
+}
+
+/// The permanent of a real n x n matrix by Ryser's formula
+///     perm(A) = (-1)^n sum_{S subset [n]} (-1)^|S| prod_i sum_{j in S} a_ij
+/// stepping through the subsets in Gray code order, so that each one costs
+/// O(n) rather than O(n^2).
+pub fn permanent_ryser(matrix: &[f64], n: usize) -> f64 {
+    assert_eq!(matrix.len(), n * n, "the matrix should have n * n entries");
+    assert!(n < MAX_NODES, "the subsets are kept in a mask of {} bits", MAX_NODES);
+    if n == 0 {
+        return 1.0
+    }
+    let mut row_sums = vec![0.0; n];
+    let mut subset = 0usize;
+    let mut total = 0.0;
+    for step in 1..(1usize << n) {
+        // the column that changes between consecutive Gray codes
+        let column = step.trailing_zeros() as usize;
+        subset ^= 1 << column;
+        let sign = if subset & (1 << column) != 0 { 1.0 } else { -1.0 };
+        for (i, row_sum) in row_sums.iter_mut().enumerate() {
+            *row_sum += sign * matrix[i * n + column];
+        }
+        let term = row_sums.iter().product::<f64>();
+        if subset.count_ones().is_multiple_of(2) {
+            total += term;
+        } else {
+            total -= term;
+        }
+    }
+    if n.is_multiple_of(2) { total } else { -total }
+}
+
+/// The permanent of a real n x n matrix by Glynn's formula
+///     perm(A) = 2^(1 - n) sum_d (prod_k d_k) prod_j sum_i d_i a_ij
+/// over the sign vectors d with d_0 = 1, again in Gray code order. This is
+/// the same cost as Ryser's formula, but tends to lose less to cancellation.
+pub fn permanent_glynn(matrix: &[f64], n: usize) -> f64 {
+    assert_eq!(matrix.len(), n * n, "the matrix should have n * n entries");
+    assert!(n < MAX_NODES, "the sign vectors are kept in a mask of {} bits", MAX_NODES);
+    if n == 0 {
+        return 1.0
+    }
+    let mut column_sums = (0..n)
+        .map(|j| (0..n).map(|i| matrix[i * n + j]).sum::<f64>())
+        .collect::<Vec<_>>();
+    let mut negated_rows = 0usize;
+    let mut total = column_sums.iter().product::<f64>();
+    for step in 1..(1usize << (n - 1)) {
+        let row = step.trailing_zeros() as usize + 1;
+        negated_rows ^= 1 << row;
+        let change = if negated_rows & (1 << row) != 0 { -2.0 } else { 2.0 };
+        for (j, column_sum) in column_sums.iter_mut().enumerate() {
+            *column_sum += change * matrix[row * n + j];
+        }
+        let term = column_sums.iter().product::<f64>();
+        if negated_rows.count_ones().is_multiple_of(2) {
+            total += term;
+        } else {
+            total -= term;
+        }
+    }
+    total / (1u64 << (n - 1)) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rook_polynomial() {
+        assert_eq!(rook_polynomial(&[1; 4], 2, 2), vec![1, 4, 2]);
+        assert_eq!(rook_polynomial(&[1; 9], 3, 3), vec![1, 9, 18, 6]);
+        // a 2 x 3 board missing its corners (0, 0) and (1, 2)
+        let board = [
+            0, 1, 1,
+            1, 1, 0,
+        ];
+        assert_eq!(rook_polynomial(&board, 2, 3), vec![1, 4, 3]);
+        assert_eq!(permanent_01(&[1; 16], 4), 24);
+    }
+
+    #[test]
+    fn test_permanent() {
+        let matrix = [
+            1.0, 2.0, 3.0,
+            4.0, 5.0, 6.0,
+            7.0, 8.0, 9.0,
+        ];
+        assert_eq!(permanent_ryser(&matrix, 3), 450.0);
+        assert_eq!(permanent_glynn(&matrix, 3), 450.0);
+        assert!((permanent(&matrix, 3) - 450.0).abs() < 1e-3);
+
+        // the empty matrix has the empty permutation
+        assert_eq!(permanent_ryser(&[], 0), 1.0);
+        assert_eq!(permanent_glynn(&[], 0), 1.0);
+        assert_eq!(permanent(&[], 0), 1.0);
+
+        for n in [4, 7] {
+            let matrix = (0..n * n)
+                .map(|k| ((k * 7) % 5) as f64 * 0.5 - 0.75)
+                .collect::<Vec<_>>();
+            let ryser = permanent_ryser(&matrix, n);
+            assert!((ryser - permanent_glynn(&matrix, n)).abs() < 1e-9);
+            if n == 4 {
+                assert!((ryser - permanent(&matrix, n)).abs() < 1e-3);
+            }
+        }
+
+        let board = [
+            1, 1, 0, 1,
+            0, 1, 1, 1,
+            1, 0, 1, 1,
+            1, 1, 1, 0,
+        ];
+        let as_matrix = board.map(|x| x as f64);
+        assert_eq!(permanent_ryser(&as_matrix, 4), permanent_01(&board, 4) as f64);
+    }
+}
@@ -0,0 +1,208 @@
+use crate::binary_graph_matching::{BinaryGraph, calculate_matching_polynomial_pointer};
+use crate::traits::Graph;
+use std::collections::VecDeque;
+use std::mem::size_of;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+
+/// The matching number nu(G), i.e. the size of a maximum matching, read off the
+/// matching polynomial: the lowest non-zero coefficient of the signless
+/// polynomial belongs to x^(n - 2 nu).
+pub fn matching_number(graph: BinaryGraph) -> usize {
+    let graph_size = graph.graph_size();
+    let matching_polynomial = calculate_matching_polynomial_pointer(graph);
+    let lowest_power = matching_polynomial
+        .iter()
+        .position(|x| *x != 0)
+        .unwrap_or(graph_size);
+    (graph_size - lowest_power) / 2
+}
+
+/// Finds a maximum matching with Edmonds' blossom algorithm. We grow an
+/// alternating tree from each unmatched node in turn by breadth-first search;
+/// when an edge closes an odd cycle, the cycle (the blossom) is contracted onto
+/// its base by relabelling the bases of its nodes, and when the search reaches
+/// an unmatched node the augmenting path is flipped.
+///
+/// The matching is returned as (u, v) pairs with u < v, in the labels of the
+/// graph.
+pub fn maximum_matching(graph: BinaryGraph) -> Vec<(usize, usize)> {
+    let neighbourhoods = graph.neighbourhoods();
+    let node_mask = graph.node_mask();
+    let mut blossom = Blossom::new(&neighbourhoods, node_mask);
+
+    for root in 0..MAX_NODES {
+        if node_mask & (1 << root) == 0 || blossom.mate[root].is_some() {
+            continue
+        }
+        if let Some(end) = blossom.find_augmenting_path(root) {
+            blossom.augment(end);
+        }
+    }
+
+    (0..MAX_NODES)
+        .filter_map(|node| match blossom.mate[node] {
+            Some(partner) if node < partner => Some((node, partner)),
+            _ => None,
+        })
+        .collect()
+}
+
+struct Blossom<'a> {
+    neighbourhoods: &'a [usize; MAX_NODES],
+    node_mask: usize,
+    mate: [Option<usize>; MAX_NODES],
+    parent: [Option<usize>; MAX_NODES],
+    base: [usize; MAX_NODES],
+    used: [bool; MAX_NODES],
+}
+
+impl<'a> Blossom<'a> {
+    fn new(neighbourhoods: &'a [usize; MAX_NODES], node_mask: usize) -> Blossom<'a> {
+        Blossom {
+            neighbourhoods,
+            node_mask,
+            mate: [None; MAX_NODES],
+            parent: [None; MAX_NODES],
+            base: [0; MAX_NODES],
+            used: [false; MAX_NODES],
+        }
+    }
+
+    /// the lowest common ancestor of two even nodes in the alternating tree,
+    /// in terms of the bases of their blossoms
+    fn lowest_common_ancestor(&self, mut a: usize, mut b: usize) -> usize {
+        let mut seen = [false; MAX_NODES];
+        loop {
+            a = self.base[a];
+            seen[a] = true;
+            match self.mate[a] {
+                Some(partner) => a = self.parent[partner].unwrap(),
+                None => break,
+            }
+        }
+        loop {
+            b = self.base[b];
+            if seen[b] {
+                return b
+            }
+            b = self.parent[self.mate[b].unwrap()].unwrap();
+        }
+    }
+
+    /// marks the blossom along the path from v up to its base, pointing the
+    /// odd nodes on the path back across the edge that closed the cycle
+    fn mark_path(&mut self, mut node: usize, base: usize, mut child: usize, in_blossom: &mut [bool; MAX_NODES]) {
+        while self.base[node] != base {
+            let partner = self.mate[node].unwrap();
+            in_blossom[self.base[node]] = true;
+            in_blossom[self.base[partner]] = true;
+            self.parent[node] = Some(child);
+            child = partner;
+            node = self.parent[partner].unwrap();
+        }
+    }
+
+    fn find_augmenting_path(&mut self, root: usize) -> Option<usize> {
+        self.used = [false; MAX_NODES];
+        self.parent = [None; MAX_NODES];
+        for i in 0..MAX_NODES {
+            self.base[i] = i;
+        }
+        self.used[root] = true;
+        let mut queue = VecDeque::from([root]);
+
+        while let Some(node) = queue.pop_front() {
+            let mut neighbours = self.neighbourhoods[node] & self.node_mask;
+            while neighbours != 0 {
+                let to = neighbours.trailing_zeros() as usize;
+                neighbours &= neighbours - 1;
+                if self.base[node] == self.base[to] || self.mate[node] == Some(to) {
+                    continue
+                }
+                let closes_cycle = to == root || self.mate[to].is_some_and(|partner| self.parent[partner].is_some());
+                if closes_cycle {
+                    // contract the blossom onto the common base
+                    let current_base = self.lowest_common_ancestor(node, to);
+                    let mut in_blossom = [false; MAX_NODES];
+                    self.mark_path(node, current_base, to, &mut in_blossom);
+                    self.mark_path(to, current_base, node, &mut in_blossom);
+                    for i in 0..MAX_NODES {
+                        if self.node_mask & (1 << i) != 0 && in_blossom[self.base[i]] {
+                            self.base[i] = current_base;
+                            if !self.used[i] {
+                                self.used[i] = true;
+                                queue.push_back(i);
+                            }
+                        }
+                    }
+                } else if self.parent[to].is_none() {
+                    self.parent[to] = Some(node);
+                    match self.mate[to] {
+                        None => return Some(to),
+                        Some(partner) => {
+                            self.used[partner] = true;
+                            queue.push_back(partner);
+                        },
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// flips the matched and unmatched edges along the path ending at `end`
+    fn augment(&mut self, end: usize) {
+        let mut node = Some(end);
+        while let Some(current) = node {
+            let parent = self.parent[current].unwrap();
+            let next = self.mate[parent];
+            self.mate[current] = Some(parent);
+            self.mate[parent] = Some(current);
+            node = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maximum_matching() {
+        // the 5-cycle with a pendant node has a perfect matching
+        let data = [
+            0b110010, 0b11000, 0b1100, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        // two triangles joined by a path, which needs a blossom to be found
+        let blossom_data = [
+            0b11100000, 0b1100000, 0b110000, 0b11000, 0b1100, 0b111, 0b11, 0b1,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let standard_data_missing = [
+            0b11001, 0b1001, 0, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0,
+        ];
+        for (data, expected) in [(data, 3), (blossom_data, 4), (standard_data_missing, 2)] {
+            let graph = BinaryGraph::from_graph_subset(data, BinaryGraph::from(data).adjacency_width());
+            let matching = maximum_matching(graph);
+            assert_eq!(matching.len(), expected);
+            assert_eq!(matching_number(graph), expected);
+
+            // check that it is a matching in the graph
+            let edges = graph.edges();
+            let mut covered = 0;
+            for (u, v) in matching {
+                assert!(edges.contains(&(u, v)));
+                assert_eq!(covered & ((1 << u) | (1 << v)), 0);
+                covered |= (1 << u) | (1 << v);
+            }
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod blossom;
+mod weighted_blossom;
+
+pub use self::blossom::{matching_number, maximum_matching};
+pub use self::weighted_blossom::{maximum_weight_matching, matching_weight};
@@ -0,0 +1,686 @@
+use crate::weighted_graph_matching::WeightedGraph;
+use crate::traits::Graph;
+
+/// Finds a maximum-weight matching with the primal-dual weighted blossom
+/// algorithm (Edmonds; this follows the layout of Van Rantwijk's
+/// implementation). Each stage grows alternating trees from the unmatched
+/// nodes through edges of zero slack, contracting odd cycles into blossoms
+/// as it goes, and adjusts the dual variables whenever it gets stuck. A stage
+/// ends either with an augmentation or when a node's dual reaches zero, at
+/// which point no augmentation can raise the weight.
+///
+/// The weight of an edge (u, v), u < v, is read from
+/// weights[u * initial_graph_size + v], as in the weighted engines. Edges with
+/// non-positive weight are never used. The matching is returned as (u, v)
+/// pairs with u < v.
+pub fn maximum_weight_matching(weighted_graph: &WeightedGraph) -> Vec<(usize, usize)> {
+    let graph = weighted_graph.graph;
+    let initial_graph_size = graph.initial_graph_size();
+    let edges = graph.edges();
+    if edges.is_empty() {
+        return Vec::new()
+    }
+
+    // the algorithm works on the nodes that remain, relabelled 0..n
+    let node_mask = graph.node_mask();
+    let nodes = (0..usize::BITS as usize)
+        .filter(|node| node_mask & (1 << node) != 0)
+        .collect::<Vec<_>>();
+    let mut labels = vec![0; usize::BITS as usize];
+    for (index, node) in nodes.iter().enumerate() {
+        labels[*node] = index;
+    }
+
+    let node_count = nodes.len();
+    let mut weights = vec![0.0; node_count * node_count];
+    let mut neighbours = vec![Vec::new(); node_count];
+    for (start_node, end_node) in edges {
+        let weight = weighted_graph.weights[start_node * initial_graph_size + end_node] as f64;
+        let (i, j) = (labels[start_node], labels[end_node]);
+        weights[i * node_count + j] = weight;
+        weights[j * node_count + i] = weight;
+        neighbours[i].push(j);
+        neighbours[j].push(i);
+    }
+
+    let mut blossom = WeightedBlossom::new(node_count, weights, neighbours);
+    blossom.solve();
+
+    let mut matching = Vec::new();
+    for (i, mate) in blossom.mate.iter().enumerate() {
+        if let Some(j) = mate {
+            if i < *j && blossom.weight(i, *j) > 0.0 {
+                matching.push((nodes[i], nodes[*j]));
+            }
+        }
+    }
+    matching
+}
+
+/// The total weight of the edges in a matching
+pub fn matching_weight(weighted_graph: &WeightedGraph, matching: &[(usize, usize)]) -> f32 {
+    let initial_graph_size = weighted_graph.graph.initial_graph_size();
+    matching
+        .iter()
+        .map(|(u, v)| weighted_graph.weights[u.min(v) * initial_graph_size + u.max(v)])
+        .sum()
+}
+
+// labels of the top-level blossoms
+const FREE: u8 = 0;
+const S_LABEL: u8 = 1;
+const T_LABEL: u8 = 2;
+const BREADCRUMB: u8 = 4;
+
+/// The state of the weighted blossom algorithm. Indices 0..n are the nodes
+/// (trivial blossoms) and n..2n are the ids available for non-trivial
+/// blossoms. The duals of both live in `dual`.
+struct WeightedBlossom {
+    node_count: usize,
+    weights: Vec<f64>,
+    neighbours: Vec<Vec<usize>>,
+    mate: Vec<Option<usize>>,
+    label: Vec<u8>,
+    label_edge: Vec<Option<(usize, usize)>>,
+    in_blossom: Vec<usize>,
+    blossom_parent: Vec<Option<usize>>,
+    blossom_children: Vec<Vec<usize>>,
+    blossom_edges: Vec<Vec<(usize, usize)>>,
+    blossom_base: Vec<usize>,
+    best_edge: Vec<Option<(usize, usize)>>,
+    blossom_best_edges: Vec<Option<Vec<(usize, usize)>>>,
+    dual: Vec<f64>,
+    in_use: Vec<bool>,
+    unused_blossoms: Vec<usize>,
+    allowed_edge: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+impl WeightedBlossom {
+    fn new(node_count: usize, weights: Vec<f64>, neighbours: Vec<Vec<usize>>) -> WeightedBlossom {
+        let max_weight = weights.iter().cloned().fold(0.0, f64::max);
+        let mut dual = vec![max_weight; node_count];
+        dual.extend(vec![0.0; node_count]);
+        WeightedBlossom {
+            node_count,
+            weights,
+            neighbours,
+            mate: vec![None; node_count],
+            label: vec![FREE; 2 * node_count],
+            label_edge: vec![None; 2 * node_count],
+            in_blossom: (0..node_count).collect(),
+            blossom_parent: vec![None; 2 * node_count],
+            blossom_children: vec![Vec::new(); 2 * node_count],
+            blossom_edges: vec![Vec::new(); 2 * node_count],
+            blossom_base: (0..node_count).chain(vec![usize::MAX; node_count]).collect(),
+            best_edge: vec![None; 2 * node_count],
+            blossom_best_edges: vec![None; 2 * node_count],
+            dual,
+            in_use: vec![false; 2 * node_count],
+            unused_blossoms: (node_count..2 * node_count).rev().collect(),
+            allowed_edge: vec![false; node_count * node_count],
+            queue: Vec::new(),
+        }
+    }
+
+    fn weight(&self, v: usize, w: usize) -> f64 {
+        self.weights[v * self.node_count + w]
+    }
+
+    fn slack(&self, (v, w): (usize, usize)) -> f64 {
+        self.dual[v] + self.dual[w] - 2.0 * self.weight(v, w)
+    }
+
+    fn allow(&mut self, (v, w): (usize, usize)) {
+        self.allowed_edge[v * self.node_count + w] = true;
+        self.allowed_edge[w * self.node_count + v] = true;
+    }
+
+    fn is_blossom(&self, b: usize) -> bool {
+        b >= self.node_count
+    }
+
+    /// the top-level blossoms, trivial ones included
+    fn top_level_blossoms(&self) -> Vec<usize> {
+        (0..2 * self.node_count)
+            .filter(|b| (*b < self.node_count || self.in_use[*b]) && self.blossom_parent[*b].is_none())
+            .collect()
+    }
+
+    /// the non-trivial top-level blossoms
+    fn top_level_non_trivial_blossoms(&self) -> Vec<usize> {
+        (self.node_count..2 * self.node_count)
+            .filter(|b| self.in_use[*b] && self.blossom_parent[*b].is_none())
+            .collect()
+    }
+
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        if !self.is_blossom(b) {
+            return vec![b]
+        }
+        self.blossom_children[b]
+            .iter()
+            .flat_map(|child| self.leaves(*child))
+            .collect()
+    }
+
+    /// labels the top-level blossom containing w, reached through the edge
+    /// (v, w), and carries on to the partner of its base if it becomes T
+    fn assign_label(&mut self, w: usize, label: u8, v: Option<usize>) {
+        let b = self.in_blossom[w];
+        self.label[w] = label;
+        self.label[b] = label;
+        let edge = v.map(|v| (v, w));
+        self.label_edge[w] = edge;
+        self.label_edge[b] = edge;
+        self.best_edge[w] = None;
+        self.best_edge[b] = None;
+        if label == S_LABEL {
+            let leaves = self.leaves(b);
+            self.queue.extend(leaves);
+        } else if label == T_LABEL {
+            let base = self.blossom_base[b];
+            self.assign_label(self.mate[base].unwrap(), S_LABEL, Some(base));
+        }
+    }
+
+    /// traces back from v and w to find either the base of a new blossom or
+    /// that they lie in different trees, i.e. an augmenting path
+    fn scan_blossom(&mut self, v: usize, w: usize) -> Option<usize> {
+        let mut path = Vec::new();
+        let mut base = None;
+        let mut v = Some(v);
+        let mut w = Some(w);
+        while let Some(current) = v {
+            let b = self.in_blossom[current];
+            if self.label[b] & BREADCRUMB != 0 {
+                base = Some(self.blossom_base[b]);
+                break
+            }
+            path.push(b);
+            self.label[b] = S_LABEL | BREADCRUMB;
+            v = match self.label_edge[b] {
+                None => None,
+                Some((t, _)) => self.label_edge[self.in_blossom[t]].map(|(u, _)| u),
+            };
+            if w.is_some() {
+                std::mem::swap(&mut v, &mut w);
+            }
+        }
+        for b in path {
+            self.label[b] = S_LABEL;
+        }
+        base
+    }
+
+    /// contracts the cycle closed by the edge (v, w) into a new blossom
+    fn add_blossom(&mut self, base: usize, v: usize, w: usize) {
+        let base_blossom = self.in_blossom[base];
+        let mut v_blossom = self.in_blossom[v];
+        let mut w_blossom = self.in_blossom[w];
+        let b = self.unused_blossoms.pop().unwrap();
+        self.in_use[b] = true;
+        self.blossom_base[b] = base;
+        self.blossom_parent[b] = None;
+        self.blossom_parent[base_blossom] = Some(b);
+
+        let mut path = Vec::new();
+        let mut edges = vec![(v, w)];
+        while v_blossom != base_blossom {
+            self.blossom_parent[v_blossom] = Some(b);
+            path.push(v_blossom);
+            let edge = self.label_edge[v_blossom].unwrap();
+            edges.push(edge);
+            v_blossom = self.in_blossom[edge.0];
+        }
+        path.push(base_blossom);
+        path.reverse();
+        edges.reverse();
+        while w_blossom != base_blossom {
+            self.blossom_parent[w_blossom] = Some(b);
+            path.push(w_blossom);
+            let (t, u) = self.label_edge[w_blossom].unwrap();
+            edges.push((u, t));
+            w_blossom = self.in_blossom[t];
+        }
+
+        self.label[b] = S_LABEL;
+        self.label_edge[b] = self.label_edge[base_blossom];
+        self.dual[b] = 0.0;
+        self.blossom_children[b] = path.clone();
+        self.blossom_edges[b] = edges;
+
+        for leaf in self.leaves(b) {
+            if self.label[self.in_blossom[leaf]] == T_LABEL {
+                // T nodes become S nodes inside the blossom
+                self.queue.push(leaf);
+            }
+            self.in_blossom[leaf] = b;
+        }
+
+        // the least-slack edge from the new blossom to each other S blossom
+        let mut best_edge_to = vec![None; 2 * self.node_count];
+        for child in path {
+            let neighbour_edges = if self.is_blossom(child) {
+                match self.blossom_best_edges[child].take() {
+                    Some(edges) => edges,
+                    None => self.leaves(child)
+                        .into_iter()
+                        .flat_map(|leaf| self.neighbours[leaf].iter().map(move |w| (leaf, *w)))
+                        .collect(),
+                }
+            } else {
+                self.neighbours[child].iter().map(|w| (child, *w)).collect()
+            };
+            for (i, j) in neighbour_edges {
+                let (i, j) = if self.in_blossom[j] == b { (j, i) } else { (i, j) };
+                let j_blossom = self.in_blossom[j];
+                if j_blossom != b && self.label[j_blossom] == S_LABEL {
+                    let better = match best_edge_to[j_blossom] {
+                        None => true,
+                        Some(edge) => self.slack((i, j)) < self.slack(edge),
+                    };
+                    if better {
+                        best_edge_to[j_blossom] = Some((i, j));
+                    }
+                }
+            }
+            self.best_edge[child] = None;
+        }
+        let best_edges = best_edge_to.into_iter().flatten().collect::<Vec<_>>();
+        let mut best_edge: Option<(usize, usize)> = None;
+        for edge in best_edges.iter() {
+            if best_edge.is_none_or(|best| self.slack(*edge) < self.slack(best)) {
+                best_edge = Some(*edge);
+            }
+        }
+        self.blossom_best_edges[b] = Some(best_edges);
+        self.best_edge[b] = best_edge;
+    }
+
+    /// expands a blossom back into its children; when this happens mid-stage
+    /// to a T blossom, the part of the cycle on the alternating path is
+    /// relabelled so that the tree stays valid
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        for child in self.blossom_children[b].clone() {
+            self.blossom_parent[child] = None;
+            if self.is_blossom(child) {
+                if end_stage && self.dual[child] == 0.0 {
+                    self.expand_blossom(child, end_stage);
+                } else {
+                    for leaf in self.leaves(child) {
+                        self.in_blossom[leaf] = child;
+                    }
+                }
+            } else {
+                self.in_blossom[child] = child;
+            }
+        }
+
+        if !end_stage && self.label[b] == T_LABEL {
+            let children = self.blossom_children[b].clone();
+            let edges = self.blossom_edges[b].clone();
+            let length = children.len() as isize;
+            let index = |j: isize| j.rem_euclid(length) as usize;
+
+            let entry_child = self.in_blossom[self.label_edge[b].unwrap().1];
+            let mut j = children.iter().position(|c| *c == entry_child).unwrap() as isize;
+            let step = if j & 1 == 1 {
+                j -= length;
+                1
+            } else {
+                -1
+            };
+
+            // relabel the even-length path from the entry child to the base
+            let (mut v, mut w) = self.label_edge[b].unwrap();
+            while j != 0 {
+                let (p, q) = if step == 1 {
+                    edges[index(j)]
+                } else {
+                    let (q, p) = edges[index(j - 1)];
+                    (p, q)
+                };
+                self.label[w] = FREE;
+                self.label[q] = FREE;
+                self.assign_label(w, T_LABEL, Some(v));
+                self.allow((p, q));
+                j += step;
+                (v, w) = if step == 1 {
+                    edges[index(j)]
+                } else {
+                    let (w, v) = edges[index(j - 1)];
+                    (v, w)
+                };
+                self.allow((v, w));
+                j += step;
+            }
+
+            let base_child = children[index(j)];
+            self.label[w] = T_LABEL;
+            self.label[base_child] = T_LABEL;
+            self.label_edge[w] = Some((v, w));
+            self.label_edge[base_child] = Some((v, w));
+            self.best_edge[base_child] = None;
+
+            // the rest of the cycle is free unless a node in it was reached
+            j += step;
+            while children[index(j)] != entry_child {
+                let child = children[index(j)];
+                if self.label[child] == S_LABEL {
+                    j += step;
+                    continue
+                }
+                let reached = self.leaves(child)
+                    .into_iter()
+                    .find(|leaf| self.label[*leaf] != FREE);
+                if let Some(leaf) = reached {
+                    self.label[leaf] = FREE;
+                    let partner = self.mate[self.blossom_base[child]].unwrap();
+                    self.label[partner] = FREE;
+                    let (u, _) = self.label_edge[leaf].unwrap();
+                    self.assign_label(leaf, T_LABEL, Some(u));
+                }
+                j += step;
+            }
+        }
+
+        self.label[b] = FREE;
+        self.label_edge[b] = None;
+        self.best_edge[b] = None;
+        self.blossom_parent[b] = None;
+        self.blossom_base[b] = usize::MAX;
+        self.blossom_children[b].clear();
+        self.blossom_edges[b].clear();
+        self.blossom_best_edges[b] = None;
+        self.dual[b] = 0.0;
+        self.in_use[b] = false;
+        self.unused_blossoms.push(b);
+    }
+
+    /// swaps the matched and unmatched edges along the even path from v to
+    /// the base of b, making v the new base
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.blossom_parent[t] != Some(b) {
+            t = self.blossom_parent[t].unwrap();
+        }
+        if self.is_blossom(t) {
+            self.augment_blossom(t, v);
+        }
+
+        let length = self.blossom_children[b].len() as isize;
+        let index = |j: isize| j.rem_euclid(length) as usize;
+        let i = self.blossom_children[b].iter().position(|c| *c == t).unwrap();
+        let mut j = i as isize;
+        let step = if i & 1 == 1 {
+            j -= length;
+            1
+        } else {
+            -1
+        };
+
+        while j != 0 {
+            j += step;
+            let t = self.blossom_children[b][index(j)];
+            let (w, x) = if step == 1 {
+                self.blossom_edges[b][index(j)]
+            } else {
+                let (x, w) = self.blossom_edges[b][index(j - 1)];
+                (w, x)
+            };
+            if self.is_blossom(t) {
+                self.augment_blossom(t, w);
+            }
+            j += step;
+            let t = self.blossom_children[b][index(j)];
+            if self.is_blossom(t) {
+                self.augment_blossom(t, x);
+            }
+            self.mate[w] = Some(x);
+            self.mate[x] = Some(w);
+        }
+
+        self.blossom_children[b].rotate_left(i);
+        self.blossom_edges[b].rotate_left(i);
+        self.blossom_base[b] = self.blossom_base[self.blossom_children[b][0]];
+    }
+
+    /// augments the matching along the path through the edge (v, w) joining
+    /// two S nodes in different trees
+    fn augment_matching(&mut self, v: usize, w: usize) {
+        for (mut s, mut j) in [(v, w), (w, v)] {
+            loop {
+                let s_blossom = self.in_blossom[s];
+                if self.is_blossom(s_blossom) {
+                    self.augment_blossom(s_blossom, s);
+                }
+                self.mate[s] = Some(j);
+                let (t, _) = match self.label_edge[s_blossom] {
+                    None => break,
+                    Some(edge) => edge,
+                };
+                let t_blossom = self.in_blossom[t];
+                (s, j) = self.label_edge[t_blossom].unwrap();
+                if self.is_blossom(t_blossom) {
+                    self.augment_blossom(t_blossom, j);
+                }
+                self.mate[j] = Some(s);
+            }
+        }
+    }
+
+    fn solve(&mut self) {
+        let node_count = self.node_count;
+        loop {
+            // start a new stage
+            self.label.iter_mut().for_each(|x| *x = FREE);
+            self.label_edge.iter_mut().for_each(|x| *x = None);
+            self.best_edge.iter_mut().for_each(|x| *x = None);
+            self.blossom_best_edges.iter_mut().for_each(|x| *x = None);
+            self.allowed_edge.iter_mut().for_each(|x| *x = false);
+            self.queue.clear();
+
+            for v in 0..node_count {
+                if self.mate[v].is_none() && self.label[self.in_blossom[v]] == FREE {
+                    self.assign_label(v, S_LABEL, None);
+                }
+            }
+
+            let mut augmented = false;
+            loop {
+                while let Some(v) = self.queue.pop() {
+                    for w in self.neighbours[v].clone() {
+                        let v_blossom = self.in_blossom[v];
+                        let w_blossom = self.in_blossom[w];
+                        if v_blossom == w_blossom {
+                            continue
+                        }
+                        let mut edge_slack = 0.0;
+                        if !self.allowed_edge[v * node_count + w] {
+                            edge_slack = self.slack((v, w));
+                            if edge_slack <= 0.0 {
+                                self.allow((v, w));
+                            }
+                        }
+                        if self.allowed_edge[v * node_count + w] {
+                            if self.label[w_blossom] == FREE {
+                                self.assign_label(w, T_LABEL, Some(v));
+                            } else if self.label[w_blossom] == S_LABEL {
+                                match self.scan_blossom(v, w) {
+                                    Some(base) => self.add_blossom(base, v, w),
+                                    None => {
+                                        self.augment_matching(v, w);
+                                        augmented = true;
+                                        break
+                                    },
+                                }
+                            } else if self.label[w] == FREE {
+                                // w is inside a T blossom but not yet reached
+                                self.label[w] = T_LABEL;
+                                self.label_edge[w] = Some((v, w));
+                            }
+                        } else if self.label[w_blossom] == S_LABEL {
+                            if self.best_edge[v_blossom].is_none_or(|edge| edge_slack < self.slack(edge)) {
+                                self.best_edge[v_blossom] = Some((v, w));
+                            }
+                        } else if self.label[w] == FREE
+                            && self.best_edge[w].is_none_or(|edge| edge_slack < self.slack(edge)) {
+                            self.best_edge[w] = Some((v, w));
+                        }
+                    }
+                    if augmented {
+                        break
+                    }
+                }
+                if augmented {
+                    break
+                }
+
+                // no progress with tight edges, so work out the dual change
+                let mut delta_type = 1;
+                let mut delta = self.dual[..node_count].iter().cloned().fold(f64::INFINITY, f64::min);
+                let mut delta_edge = None;
+                let mut delta_blossom = None;
+
+                for v in 0..node_count {
+                    if self.label[self.in_blossom[v]] == FREE {
+                        if let Some(edge) = self.best_edge[v] {
+                            let d = self.slack(edge);
+                            if d < delta {
+                                delta = d;
+                                delta_type = 2;
+                                delta_edge = Some(edge);
+                            }
+                        }
+                    }
+                }
+                for b in self.top_level_blossoms() {
+                    if self.label[b] == S_LABEL {
+                        if let Some(edge) = self.best_edge[b] {
+                            let d = self.slack(edge) / 2.0;
+                            if d < delta {
+                                delta = d;
+                                delta_type = 3;
+                                delta_edge = Some(edge);
+                            }
+                        }
+                    }
+                }
+                for b in self.top_level_non_trivial_blossoms() {
+                    if self.label[b] == T_LABEL && self.dual[b] < delta {
+                        delta = self.dual[b];
+                        delta_type = 4;
+                        delta_blossom = Some(b);
+                    }
+                }
+
+                for v in 0..node_count {
+                    match self.label[self.in_blossom[v]] {
+                        S_LABEL => self.dual[v] -= delta,
+                        T_LABEL => self.dual[v] += delta,
+                        _ => {},
+                    }
+                }
+                for b in self.top_level_non_trivial_blossoms() {
+                    match self.label[b] {
+                        S_LABEL => self.dual[b] += delta,
+                        T_LABEL => self.dual[b] -= delta,
+                        _ => {},
+                    }
+                }
+
+                match delta_type {
+                    1 => break,
+                    2 | 3 => {
+                        let (v, w) = delta_edge.unwrap();
+                        self.allow((v, w));
+                        self.queue.push(v);
+                    },
+                    _ => self.expand_blossom(delta_blossom.unwrap(), false),
+                }
+            }
+
+            if !augmented {
+                break
+            }
+
+            // S blossoms whose dual has dropped to zero are expanded
+            for b in node_count..2 * node_count {
+                if self.in_use[b] && self.blossom_parent[b].is_none()
+                    && self.label[b] == S_LABEL && self.dual[b] == 0.0 {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_graph_matching::matchings;
+    use std::mem::size_of;
+
+    const MAX_NODES: usize = size_of::<usize>()*8;
+
+    fn brute_force_weight(weighted_graph: &WeightedGraph) -> f32 {
+        matchings(weighted_graph.graph)
+            .map(|matching| matching_weight(weighted_graph, &matching))
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_maximum_weight_matching() {
+        // a path on four nodes where the heavy middle edge beats the two ends
+        let chain_data = [
+            0b1100, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        let mut weights = [0.0; MAX_NODES*MAX_NODES];
+        weights[1] = 2.0;
+        weights[4 + 2] = 5.0;
+        weights[2 * 4 + 3] = 2.0;
+        let weighted_graph = WeightedGraph::from(chain_data, weights);
+        assert_eq!(maximum_weight_matching(&weighted_graph), vec![(1, 2)]);
+    }
+
+    #[test]
+    fn test_maximum_weight_matching_against_enumeration() {
+        // the complete graph on six nodes and the 5-cycle with a pendant node,
+        // with assorted weights so that blossoms have to be formed and expanded
+        let fc_data = [
+            0b111111, 0b11111, 0b1111, 0b111, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let cycle_data = [
+            0b110010, 0b11000, 0b1100, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        for data in [fc_data, cycle_data] {
+            for seed in 0..20 {
+                let mut weights = [0.0; MAX_NODES*MAX_NODES];
+                for i in 0..6 {
+                    for j in (i + 1)..6 {
+                        weights[i * 6 + j] = ((i * 7 + j * 13 + seed * 5) % 11 + 1) as f32;
+                    }
+                }
+                let weighted_graph = WeightedGraph::from(data, weights);
+                let matching = maximum_weight_matching(&weighted_graph);
+                assert_eq!(matching_weight(&weighted_graph, &matching), brute_force_weight(&weighted_graph));
+
+                let edges = weighted_graph.graph.edges();
+                let mut covered = 0;
+                for (u, v) in matching {
+                    assert!(edges.contains(&(u, v)));
+                    assert_eq!(covered & ((1 << u) | (1 << v)), 0);
+                    covered |= (1 << u) | (1 << v);
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
 mod example_petgraphs;
 mod petgraph;
+mod path_tree;
 pub use self::petgraph::{_calculate_matching_polynomial, get_matching_polies_stable_graph};
+pub use self::path_tree::{path_tree, tree_matching_polynomial, check_path_tree_divisibility, matching_root_bound};
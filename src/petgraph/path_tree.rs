@@ -0,0 +1,187 @@
+use petgraph::stable_graph::{NodeIndex, StableGraph};
+use petgraph::visit::NodeIndexable;
+use petgraph::Undirected;
+use num::{BigInt, Zero, One};
+use crate::binary_graph_matching::{BinaryGraph, calculate_matching_polynomial_pointer};
+use crate::polynomials::signed_matching_polynomial;
+use crate::polynomials::basis::{add, sub, multiply, trim, power_div};
+use crate::traits::Graph;
+
+// Godsil's path tree T(G, v) has a node for each path in G that starts at v,
+// with each path joined to the paths that extend it by one node. It is a tree,
+// so its matching polynomial is its characteristic polynomial, and Godsil
+// showed that
+//     m(G - v) / m(G) = m(T - v) / m(T)
+// where T - v removes the root, and that m(G) divides m(T). The roots of m(G)
+// are then eigenvalues of the adjacency matrix of T, and for connected G the
+// largest root of m(G) is the spectral radius of T.
+
+/// Builds the path tree of the graph rooted at `root`. Each node of the tree
+/// is weighted with the node of the graph its path ends at, and the root is
+/// node 0 of the tree.
+pub fn path_tree(graph: &BinaryGraph, root: usize) -> StableGraph<i32, (), Undirected> {
+    let neighbourhoods = graph.neighbourhoods();
+    assert!(graph.node_mask() & (1 << root) != 0, "node {} is not in the graph", root);
+    let mut tree = StableGraph::<i32, (), Undirected>::default();
+    let root_index = tree.add_node(root as i32);
+
+    // each stack entry is the last node of a path, the nodes it has visited
+    // and its node in the tree
+    let mut stack = vec![(root, 1usize << root, root_index)];
+    while let Some((end, visited, tree_node)) = stack.pop() {
+        let mut extensions = neighbourhoods[end] & !visited;
+        while extensions != 0 {
+            let next = extensions.trailing_zeros() as usize;
+            extensions &= extensions - 1;
+            let child = tree.add_node(next as i32);
+            tree.add_edge(tree_node, child, ());
+            stack.push((next, visited | (1 << next), child));
+        }
+    }
+    tree
+}
+
+/// The signed matching polynomial of a tree, indexed by the power of x,
+/// together with that of the tree with `root` removed. Working up from the
+/// leaves, for each node v with children c
+///     m(T_v) = x prod_c m(T_c) - sum_c m(T_c - c) prod_(c' != c) m(T_c')
+/// and m(T_v - v) is the product of the m(T_c).
+pub fn tree_matching_polynomial(tree: &StableGraph<i32, (), Undirected>, root: NodeIndex) -> (Vec<BigInt>, Vec<BigInt>) {
+    // order the nodes so that each comes before its parent
+    let mut order = Vec::with_capacity(tree.node_count());
+    let mut stack = vec![(root, None)];
+    while let Some((node, parent)) = stack.pop() {
+        order.push((node, parent));
+        for neighbour in tree.neighbors(node) {
+            if Some(neighbour) != parent {
+                stack.push((neighbour, Some(node)));
+            }
+        }
+    }
+    order.reverse();
+
+    // indexed by NodeIndex, which may skip the indices of removed nodes
+    let mut with_node = vec![Vec::new(); tree.node_bound()];
+    let mut without_node = vec![Vec::new(); tree.node_bound()];
+    for (node, parent) in order {
+        let children = tree
+            .neighbors(node)
+            .filter(|neighbour| Some(*neighbour) != parent)
+            .collect::<Vec<_>>();
+        let mut product = vec![BigInt::one()];
+        let mut matched = Vec::new();
+        for child in children {
+            // sum_c m(T_c - c) prod_(c' != c) m(T_c'), built up one child at a time
+            matched = add(&multiply(&matched, &with_node[child.index()]), &multiply(&without_node[child.index()], &product));
+            product = multiply(&product, &with_node[child.index()]);
+        }
+        let mut shifted = vec![BigInt::zero()];
+        shifted.extend(product.iter().cloned());
+        with_node[node.index()] = sub(&shifted, &matched);
+        without_node[node.index()] = product;
+    }
+    (with_node[root.index()].clone(), without_node[root.index()].clone())
+}
+
+/// Checks Godsil's theorem for the path tree rooted at `root`: that m(G)
+/// divides m(T), and that m(G - v) m(T) = m(T - v) m(G).
+pub fn check_path_tree_divisibility(graph: &BinaryGraph, root: usize) -> bool {
+    let tree = path_tree(graph, root);
+    let (tree_poly, rooted_tree_poly) = tree_matching_polynomial(&tree, NodeIndex::new(0));
+
+    let graph_size = graph.graph_size();
+    let matching_poly = signed_matching_polynomial::<BigInt>(&calculate_matching_polynomial_pointer(*graph), graph_size);
+    let mut deleted_graph = *graph;
+    deleted_graph.remove_node(root, graph.adjacency_width());
+    let deleted_poly = signed_matching_polynomial::<BigInt>(&calculate_matching_polynomial_pointer(deleted_graph), graph_size - 1);
+
+    // m(G) is monic, so the division stays in the integers
+    let (_, remainder) = power_div(&tree_poly, &matching_poly);
+    remainder.iter().all(|x| x.is_zero())
+        && trim(multiply(&deleted_poly, &tree_poly)) == trim(multiply(&rooted_tree_poly, &matching_poly))
+}
+
+/// An upper bound on the absolute values of the roots of m(G): the spectral
+/// radius of the path tree rooted at `root`, found by power iteration. For a
+/// connected graph this is the largest root itself.
+pub fn matching_root_bound(graph: &BinaryGraph, root: usize) -> f64 {
+    let tree = path_tree(graph, root);
+    if tree.edge_count() == 0 {
+        return 0.0
+    }
+    // iterate with A + I, since a tree is bipartite and so A has -r as an
+    // eigenvalue as well as r
+    let mut vector = vec![1.0 / (tree.node_bound() as f64).sqrt(); tree.node_bound()];
+    let mut radius = 0.0;
+    for _ in 0..10000 {
+        let mut next = vector.clone();
+        for edge in tree.edge_indices() {
+            let (u, v) = tree.edge_endpoints(edge).unwrap();
+            next[u.index()] += vector[v.index()];
+            next[v.index()] += vector[u.index()];
+        }
+        // the Rayleigh quotient, as the vector is kept at unit length
+        let estimate = vector.iter().zip(&next).map(|(x, y)| x * y).sum::<f64>() - 1.0;
+        let norm = next.iter().map(|x| x * x).sum::<f64>().sqrt();
+        vector = next.into_iter().map(|x| x / norm).collect();
+        if (estimate - radius).abs() < 1e-12 {
+            return estimate
+        }
+        radius = estimate;
+    }
+    radius
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_operations::graph_from_edges;
+
+    #[test]
+    fn test_path_tree() {
+        // the path tree of a path from an end is the path itself
+        let path = graph_from_edges(5, &[(0, 1), (1, 2), (2, 3), (3, 4)]);
+        let tree = path_tree(&path, 0);
+        assert_eq!((tree.node_count(), tree.edge_count()), (5, 4));
+        let bound = matching_root_bound(&path, 0);
+        assert!((bound - 2.0 * (std::f64::consts::PI / 6.0).cos()).abs() < 1e-6);
+
+        // the triangle from any node has paths v, vu, vw, vuw, vwu
+        let triangle = graph_from_edges(3, &[(0, 1), (1, 2), (0, 2)]);
+        assert_eq!(path_tree(&triangle, 1).node_count(), 5);
+        let (tree_poly, rooted_tree_poly) = tree_matching_polynomial(&path_tree(&triangle, 1), NodeIndex::new(0));
+        // T is P_5 with its centre as root: x^5 - 4x^3 + 3x, and two P_2
+        let integers = |values: &[i64]| values.iter().map(|x| BigInt::from(*x)).collect::<Vec<_>>();
+        assert_eq!(tree_poly, integers(&[0, 3, 0, -4, 0, 1]));
+        assert_eq!(trim(rooted_tree_poly), integers(&[1, 0, -2, 0, 1]));
+
+        let complete_edges = (0..5).flat_map(|u| (u + 1..5).map(move |v| (u, v))).collect::<Vec<_>>();
+        let graphs = [
+            path,
+            triangle,
+            graph_from_edges(5, &complete_edges),
+            graph_from_edges(6, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (1, 4)]),
+        ];
+        for graph in graphs {
+            for root in 0..graph.graph_size() {
+                assert!(check_path_tree_divisibility(&graph, root));
+            }
+        }
+
+        // the largest root of m(K_4) = x^4 - 6x^2 + 3 is sqrt(3 + sqrt(6))
+        let complete = graph_from_edges(4, &[(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+        let bound = matching_root_bound(&complete, 2);
+        assert!((bound - (3.0 + 6f64.sqrt()).sqrt()).abs() < 1e-6);
+
+        // a tree with a node removed, whose indices are no longer contiguous:
+        // the path 1 - 0 - 3, so x^3 - 2x, and x^2 without its root
+        let mut tree = StableGraph::<i32, (), Undirected>::default();
+        let nodes = (0..4).map(|i| tree.add_node(i)).collect::<Vec<_>>();
+        tree.remove_node(nodes[2]);
+        tree.add_edge(nodes[0], nodes[1], ());
+        tree.add_edge(nodes[0], nodes[3], ());
+        let (tree_poly, rooted_tree_poly) = tree_matching_polynomial(&tree, nodes[0]);
+        assert_eq!(tree_poly, integers(&[0, -2, 0, 1]));
+        assert_eq!(trim(rooted_tree_poly), integers(&[0, 0, 1]));
+    }
+}
@@ -0,0 +1,317 @@
+use crate::binary_graph_matching::BinaryGraph;
+use std::collections::VecDeque;
+use std::mem::size_of;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+
+/// Splits the nodes in the mask into the node masks of its connected
+/// components, given the neighbourhood masks of the graph.
+pub fn connected_components(neighbourhoods: &[usize; MAX_NODES], node_mask: usize) -> Vec<usize> {
+    let mut components = Vec::new();
+    let mut remaining_nodes = node_mask;
+    while remaining_nodes != 0 {
+        let start = remaining_nodes.trailing_zeros() as usize;
+        let mut component: usize = 1 << start;
+        let mut frontier: usize = 1 << start;
+        while frontier != 0 {
+            let node = frontier.trailing_zeros() as usize;
+            frontier &= frontier - 1;
+            let new_nodes = neighbourhoods[node] & node_mask & !component;
+            component |= new_nodes;
+            frontier |= new_nodes;
+        }
+        remaining_nodes &= !component;
+        components.push(component);
+    }
+    components
+}
+
+/// Adds edges to a connected graph until it has no cut vertices. For a cut
+/// vertex v we join two of its neighbours u and w that lie in different
+/// components of G - v. The edge uw can be drawn alongside the edges vu and
+/// vw, so the result is planar if and only if the graph was. The added edges
+/// are returned so that they can be ignored afterwards.
+pub fn make_biconnected(neighbourhoods: &mut [usize; MAX_NODES], component: usize) -> Vec<(usize, usize)> {
+    let mut added_edges = Vec::new();
+    if component.count_ones() < 3 {
+        return added_edges
+    }
+    'search: loop {
+        let mut nodes = component;
+        while nodes != 0 {
+            let node = nodes.trailing_zeros() as usize;
+            nodes &= nodes - 1;
+            let pieces = connected_components(neighbourhoods, component & !(1 << node));
+            if pieces.len() > 1 {
+                let u = (neighbourhoods[node] & pieces[0]).trailing_zeros() as usize;
+                let w = (neighbourhoods[node] & pieces[1]).trailing_zeros() as usize;
+                neighbourhoods[u] |= 1 << w;
+                neighbourhoods[w] |= 1 << u;
+                added_edges.push((u.min(w), u.max(w)));
+                continue 'search
+            }
+        }
+        break
+    }
+    added_edges
+}
+
+/// Finds a cycle in a biconnected graph by depth-first search: the first edge
+/// back to an ancestor closes a cycle through the tree path.
+fn find_cycle(neighbourhoods: &[usize; MAX_NODES], component: usize) -> Vec<usize> {
+    fn visit(node: usize, neighbourhoods: &[usize; MAX_NODES], component: usize,
+             parent: &mut [Option<usize>; MAX_NODES], visited: &mut usize) -> Option<Vec<usize>> {
+        *visited |= 1 << node;
+        let mut neighbours = neighbourhoods[node] & component;
+        while neighbours != 0 {
+            let next = neighbours.trailing_zeros() as usize;
+            neighbours &= neighbours - 1;
+            if Some(next) == parent[node] {
+                continue
+            }
+            if *visited & (1 << next) != 0 {
+                // a back edge to an ancestor
+                let mut cycle = vec![node];
+                let mut current = node;
+                while current != next {
+                    current = parent[current].unwrap();
+                    cycle.push(current);
+                }
+                return Some(cycle)
+            }
+            parent[next] = Some(node);
+            if let Some(cycle) = visit(next, neighbourhoods, component, parent, visited) {
+                return Some(cycle)
+            }
+        }
+        None
+    }
+    let mut parent = [None; MAX_NODES];
+    let mut visited = 0;
+    visit(component.trailing_zeros() as usize, neighbourhoods, component, &mut parent, &mut visited)
+        .expect("a biconnected graph with three or more nodes has a cycle")
+}
+
+fn face_mask(face: &[usize]) -> usize {
+    face.iter().fold(0, |mask, node| mask | (1 << node))
+}
+
+/// Finds the fragments of the graph relative to the embedded subgraph: the
+/// unembedded edges between embedded nodes, and the components of the
+/// unembedded nodes with the edges attaching them. Each is returned with the
+/// mask of its contact nodes and a path through it between two contacts.
+fn fragments(neighbourhoods: &[usize; MAX_NODES], component: usize,
+             embedded_nodes: usize, embedded_edges: &[usize; MAX_NODES]) -> Vec<(usize, Vec<usize>)> {
+    let mut fragments = Vec::new();
+
+    let mut nodes = embedded_nodes;
+    while nodes != 0 {
+        let node = nodes.trailing_zeros() as usize;
+        nodes &= nodes - 1;
+        let mut chords = neighbourhoods[node] & embedded_nodes & !embedded_edges[node];
+        chords &= !((1 << node) - 1) & !(1 << node);
+        while chords != 0 {
+            let other = chords.trailing_zeros() as usize;
+            chords &= chords - 1;
+            fragments.push(((1 << node) | (1 << other), vec![node, other]));
+        }
+    }
+
+    let unembedded_nodes = component & !embedded_nodes;
+    for piece in connected_components(neighbourhoods, unembedded_nodes) {
+        let mut contacts = 0;
+        let mut nodes = piece;
+        while nodes != 0 {
+            let node = nodes.trailing_zeros() as usize;
+            nodes &= nodes - 1;
+            contacts |= neighbourhoods[node] & embedded_nodes;
+        }
+
+        // a path from the first contact through the piece to another contact
+        let start = contacts.trailing_zeros() as usize;
+        let mut parent = [usize::MAX; MAX_NODES];
+        let mut queue = VecDeque::new();
+        let mut first_nodes = neighbourhoods[start] & piece;
+        while first_nodes != 0 {
+            let node = first_nodes.trailing_zeros() as usize;
+            first_nodes &= first_nodes - 1;
+            parent[node] = start;
+            queue.push_back(node);
+        }
+        let mut path = None;
+        'search: while let Some(node) = queue.pop_front() {
+            let mut neighbours = neighbourhoods[node] & (piece | embedded_nodes);
+            while neighbours != 0 {
+                let next = neighbours.trailing_zeros() as usize;
+                neighbours &= neighbours - 1;
+                if embedded_nodes & (1 << next) != 0 {
+                    if next != start {
+                        let mut nodes_on_path = vec![next, node];
+                        let mut current = node;
+                        while parent[current] != start {
+                            current = parent[current];
+                            nodes_on_path.push(current);
+                        }
+                        nodes_on_path.push(start);
+                        nodes_on_path.reverse();
+                        path = Some(nodes_on_path);
+                        break 'search
+                    }
+                } else if parent[next] == usize::MAX {
+                    parent[next] = node;
+                    queue.push_back(next);
+                }
+            }
+        }
+        fragments.push((contacts, path.expect("each fragment of a biconnected graph has two contacts")));
+    }
+    fragments
+}
+
+/// Embeds a biconnected component in the plane with the algorithm of
+/// Demoucron, Malgrange and Pertuiset, returning None if it is not planar.
+///
+/// Starting from a cycle, we repeatedly take a fragment of the rest of the
+/// graph, pick a face holding all of its contact nodes (a fragment that fits
+/// in only one face goes first), and draw a path through the fragment across
+/// that face, splitting it in two. The faces are returned as cyclic lists of
+/// nodes, all traversed in the same sense, so that each edge is used once in
+/// each direction.
+pub fn embed_biconnected(neighbourhoods: &[usize; MAX_NODES], component: usize) -> Option<Vec<Vec<usize>>> {
+    let cycle = find_cycle(neighbourhoods, component);
+    let mut embedded_nodes = face_mask(&cycle);
+    let mut embedded_edges = [0; MAX_NODES];
+    for (i, node) in cycle.iter().enumerate() {
+        let next = cycle[(i + 1) % cycle.len()];
+        embedded_edges[*node] |= 1 << next;
+        embedded_edges[next] |= 1 << node;
+    }
+    let mut reversed_cycle = cycle.clone();
+    reversed_cycle.reverse();
+    let mut faces = vec![cycle, reversed_cycle];
+
+    loop {
+        let fragments = fragments(neighbourhoods, component, embedded_nodes, &embedded_edges);
+        if fragments.is_empty() {
+            return Some(faces)
+        }
+
+        let mut chosen = None;
+        for (contacts, path) in fragments.iter() {
+            let admissible_faces = faces
+                .iter()
+                .enumerate()
+                .filter(|(_, face)| face_mask(face) & contacts == *contacts)
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>();
+            match admissible_faces.len() {
+                0 => return None,
+                1 => {
+                    chosen = Some((admissible_faces[0], path));
+                    break
+                },
+                _ => {
+                    if chosen.is_none() {
+                        chosen = Some((admissible_faces[0], path));
+                    }
+                },
+            }
+        }
+        let (face_index, path) = chosen.unwrap();
+
+        // split the face along the path from a to b
+        let face = faces.swap_remove(face_index);
+        let a = path[0];
+        let b = path[path.len() - 1];
+        let i = face.iter().position(|node| *node == a).unwrap();
+        let j = face.iter().position(|node| *node == b).unwrap();
+        let interior = &path[1..path.len() - 1];
+
+        // a -> ... -> b along the face, then back along the path
+        let mut first_face = Vec::new();
+        let mut k = i;
+        while k != j {
+            first_face.push(face[k]);
+            k = (k + 1) % face.len();
+        }
+        first_face.push(b);
+        first_face.extend(interior.iter().rev());
+
+        // b -> ... -> a along the face, then forward along the path
+        let mut second_face = Vec::new();
+        let mut k = j;
+        while k != i {
+            second_face.push(face[k]);
+            k = (k + 1) % face.len();
+        }
+        second_face.push(a);
+        second_face.extend(interior.iter());
+
+        faces.push(first_face);
+        faces.push(second_face);
+
+        for (k, node) in path.iter().enumerate().skip(1) {
+            let previous = path[k - 1];
+            embedded_edges[*node] |= 1 << previous;
+            embedded_edges[previous] |= 1 << node;
+            embedded_nodes |= 1 << node;
+        }
+    }
+}
+
+/// Tests whether the graph is planar. Each connected component is made
+/// biconnected (which does not change its planarity) and then embedded.
+pub fn is_planar(graph: BinaryGraph) -> bool {
+    let mut neighbourhoods = graph.neighbourhoods();
+    let node_mask = graph.node_mask();
+    let node_count = node_mask.count_ones() as usize;
+    let edge_count = neighbourhoods.iter().map(|x| x.count_ones() as usize).sum::<usize>() / 2;
+    if node_count >= 3 && edge_count > 3 * node_count - 6 {
+        return false
+    }
+
+    connected_components(&neighbourhoods, node_mask)
+        .into_iter()
+        .all(|component| {
+            if component.count_ones() < 3 {
+                return true
+            }
+            make_biconnected(&mut neighbourhoods, component);
+            embed_biconnected(&neighbourhoods, component).is_some()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_planar() {
+        let k4_data = [
+            0b1111, 0b111, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0
+        ];
+        let k5_data = [
+            0b11111, 0b1111, 0b111, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0
+        ];
+        // K_{3,3} on {0, 1, 2} and {3, 4, 5}
+        let k33_data = [
+            0b100111, 0b10111, 0b1111, 0b100, 0b10, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        // the 5-cycle with a pendant node
+        let pendant_data = [
+            0b110010, 0b11000, 0b1100, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        assert!(is_planar(BinaryGraph::from(k4_data)));
+        assert!(!is_planar(BinaryGraph::from(k5_data)));
+        assert!(!is_planar(BinaryGraph::from(k33_data)));
+        assert!(is_planar(BinaryGraph::from(pendant_data)));
+    }
+}
@@ -0,0 +1,230 @@
+use crate::binary_graph_matching::BinaryGraph;
+use crate::planar::embedding::{connected_components, make_biconnected, embed_biconnected};
+use num::{BigInt, Zero, One, ToPrimitive};
+use std::mem::size_of;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+
+/// Counts the perfect matchings of a planar graph in polynomial time, i.e.
+/// the constant term of its matching polynomial, or returns None if the graph
+/// is not planar.
+///
+/// Each connected component is counted separately. We embed the component
+/// (made biconnected by `make_biconnected` if need be) and give it a
+/// Kasteleyn orientation, in which every face bar the outer one has an odd
+/// number of edges oriented along its boundary. The skew-symmetric matrix of
+/// this orientation has Pfaffian equal, up to sign, to the number of perfect
+/// matchings, so the count is the square root of its determinant. The edges
+/// added to make the component biconnected are left out of the matrix; the
+/// orientation restricted to a subgraph is still Pfaffian, since its perfect
+/// matchings are a subset of those of the whole.
+pub fn count_perfect_matchings_planar(graph: BinaryGraph) -> Option<u64> {
+    let neighbourhoods = graph.neighbourhoods();
+    let mut augmented_neighbourhoods = neighbourhoods;
+    let node_mask = graph.node_mask();
+
+    let mut count = 1;
+    for component in connected_components(&neighbourhoods, node_mask) {
+        let component_size = component.count_ones();
+        if component_size < 3 {
+            // an isolated node or a single edge
+            count *= (component_size == 2) as u64;
+            continue
+        }
+        make_biconnected(&mut augmented_neighbourhoods, component);
+        let faces = embed_biconnected(&augmented_neighbourhoods, component)?;
+        if component_size % 2 == 1 {
+            count = 0;
+            continue
+        }
+        let orientation = kasteleyn_orientation(&augmented_neighbourhoods, component, &faces);
+
+        let nodes = (0..MAX_NODES)
+            .filter(|node| component & (1 << node) != 0)
+            .collect::<Vec<_>>();
+        let matrix = nodes
+            .iter()
+            .map(|u| nodes
+                .iter()
+                .map(|v| {
+                    if neighbourhoods[*u] & (1 << v) == 0 {
+                        BigInt::zero()
+                    } else if orientation[*u] & (1 << v) != 0 {
+                        BigInt::one()
+                    } else {
+                        -BigInt::one()
+                    }
+                })
+                .collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let pfaffian = determinant(matrix).sqrt();
+        count *= pfaffian.to_u64().expect("the number of perfect matchings fits in a u64");
+    }
+    Some(count)
+}
+
+/// Orients the edges of an embedded biconnected component so that each face
+/// but the first (taken as the outer face) has an odd number of edges
+/// oriented in the direction in which the face is traversed. Bit v of entry u
+/// is set if the edge is oriented u -> v.
+///
+/// The edges of a spanning tree are oriented arbitrarily. The remaining edges
+/// are dual to a spanning tree of the faces, so there is always a face, other
+/// than the outer one, with just one edge left to orient, and it can be
+/// chosen to fix that face's parity.
+pub fn kasteleyn_orientation(neighbourhoods: &[usize; MAX_NODES], component: usize, faces: &[Vec<usize>]) -> [usize; MAX_NODES] {
+    let mut orientation = [0; MAX_NODES];
+    let mut decided = [0; MAX_NODES];
+
+    // a spanning tree by breadth-first search, oriented from the root out
+    let root = component.trailing_zeros() as usize;
+    let mut reached = 1 << root;
+    let mut frontier = vec![root];
+    while let Some(node) = frontier.pop() {
+        let mut new_nodes = neighbourhoods[node] & component & !reached;
+        reached |= new_nodes;
+        while new_nodes != 0 {
+            let next = new_nodes.trailing_zeros() as usize;
+            new_nodes &= new_nodes - 1;
+            orientation[node] |= 1 << next;
+            decided[node] |= 1 << next;
+            decided[next] |= 1 << node;
+            frontier.push(next);
+        }
+    }
+
+    let face_edges = |face: &Vec<usize>| {
+        (0..face.len())
+            .map(|t| (face[t], face[(t + 1) % face.len()]))
+            .collect::<Vec<_>>()
+    };
+    let mut done = vec![false; faces.len()];
+    done[0] = true;
+    while done.iter().any(|x| !x) {
+        let mut progress = false;
+        for (index, face) in faces.iter().enumerate() {
+            if done[index] {
+                continue
+            }
+            let edges = face_edges(face);
+            let undecided = edges
+                .iter()
+                .filter(|(u, v)| decided[*u] & (1 << v) == 0)
+                .collect::<Vec<_>>();
+            if undecided.len() != 1 {
+                continue
+            }
+            let along = edges
+                .iter()
+                .filter(|(u, v)| orientation[*u] & (1 << v) != 0)
+                .count();
+            let (u, v) = *undecided[0];
+            if along % 2 == 0 {
+                orientation[u] |= 1 << v;
+            } else {
+                orientation[v] |= 1 << u;
+            }
+            decided[u] |= 1 << v;
+            decided[v] |= 1 << u;
+            done[index] = true;
+            progress = true;
+        }
+        assert!(progress, "the faces do not form an embedding of the component");
+    }
+    orientation
+}
+
+/// The determinant of an integer matrix by Bareiss' fraction-free
+/// elimination, in which every division is exact.
+fn determinant(mut matrix: Vec<Vec<BigInt>>) -> BigInt {
+    let size = matrix.len();
+    let mut sign = BigInt::one();
+    let mut previous_pivot = BigInt::one();
+    for k in 0..size {
+        if matrix[k][k].is_zero() {
+            match (k + 1..size).find(|r| !matrix[*r][k].is_zero()) {
+                Some(r) => {
+                    matrix.swap(k, r);
+                    sign = -sign;
+                },
+                None => return BigInt::zero(),
+            }
+        }
+        for i in k + 1..size {
+            for j in k + 1..size {
+                matrix[i][j] = (&matrix[i][j] * &matrix[k][k] - &matrix[i][k] * &matrix[k][j]) / &previous_pivot;
+            }
+        }
+        previous_pivot = matrix[k][k].clone();
+    }
+    match size {
+        0 => BigInt::one(),
+        _ => sign * &matrix[size - 1][size - 1],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reconstruction::count_perfect_matchings;
+
+    fn graph_from_edges(graph_size: usize, edges: &[(usize, usize)]) -> BinaryGraph {
+        let mut data = [0; MAX_NODES];
+        for (i, row) in data.iter_mut().enumerate().take(graph_size) {
+            *row = 1 << (graph_size - i - 1);
+        }
+        for (u, v) in edges {
+            data[*u.min(v)] |= 1 << (graph_size - u.max(v) - 1);
+        }
+        BinaryGraph::from(data)
+    }
+
+    #[test]
+    fn test_count_perfect_matchings_planar() {
+        // the 4 x 4 grid
+        let mut grid_edges = Vec::new();
+        for i in 0..4 {
+            for j in 0..4 {
+                if j < 3 {
+                    grid_edges.push((4 * i + j, 4 * i + j + 1));
+                }
+                if i < 3 {
+                    grid_edges.push((4 * i + j, 4 * i + j + 4));
+                }
+            }
+        }
+        // the cube
+        let cube_edges = [
+            (0, 1), (1, 2), (2, 3), (0, 3), (4, 5), (5, 6), (6, 7), (4, 7),
+            (0, 4), (1, 5), (2, 6), (3, 7),
+        ];
+        // two squares joined by a bridge: this has cut vertices, so needs
+        // edges adding before it can be embedded
+        let cut_edges = [
+            (0, 1), (1, 2), (2, 3), (0, 3), (3, 4), (4, 5), (5, 6), (6, 7), (4, 7),
+        ];
+        // a hexagon with a pendant node on each corner
+        let sun_edges = [
+            (0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (0, 5),
+            (0, 6), (1, 7), (2, 8), (3, 9), (4, 10), (5, 11),
+        ];
+        for (graph_size, edges, expected) in [
+            (16, grid_edges.as_slice(), 36),
+            (8, cube_edges.as_slice(), 9),
+            (8, cut_edges.as_slice(), 4),
+            (12, sun_edges.as_slice(), 1),
+        ] {
+            let graph = graph_from_edges(graph_size, edges);
+            assert_eq!(count_perfect_matchings_planar(graph), Some(expected));
+            assert_eq!(count_perfect_matchings(graph), expected);
+        }
+    }
+
+    #[test]
+    fn test_count_perfect_matchings_non_planar() {
+        // K_{3,3}
+        let edges = [(0, 3), (0, 4), (0, 5), (1, 3), (1, 4), (1, 5), (2, 3), (2, 4), (2, 5)];
+        let graph = graph_from_edges(6, &edges);
+        assert_eq!(count_perfect_matchings_planar(graph), None);
+    }
+}
@@ -0,0 +1,5 @@
+mod embedding;
+mod kasteleyn;
+
+pub use self::embedding::{is_planar, connected_components, make_biconnected, embed_biconnected};
+pub use self::kasteleyn::{count_perfect_matchings_planar, kasteleyn_orientation};
@@ -0,0 +1,213 @@
+// The machinery shared by the orthogonal polynomial bases. Each family here
+// satisfies a three-term recurrence
+//     x P_n = alpha_n P_(n+1) + beta_n P_n + gamma_n P_(n-1)
+// with P_0 = 1, and the numpy-style functions of each basis are thin
+// wrappers around the methods below. Multiplication by x, products,
+// evaluation and the conversions work in the basis itself through the
+// recurrence; derivatives, integrals and division go through the power
+// basis, where they are simplest and which gives the same answers as numpy's
+// basis-specific formulas.
+//
+// The coefficients are generic, so that the same recurrences serve the f32
+// functions of the numpy-style modules and the exact BigRational conversions
+// of `expansions.rs`.
+
+use num::{Num, FromPrimitive};
+
+pub(crate) trait Coefficient: Num + Clone + FromPrimitive {}
+
+impl<T: Num + Clone + FromPrimitive> Coefficient for T {}
+
+/// numerator / denominator as a coefficient
+pub(crate) fn ratio<T: Coefficient>(numerator: i64, denominator: i64) -> T {
+    T::from_i64(numerator).unwrap() / T::from_i64(denominator).unwrap()
+}
+
+pub(crate) struct Recurrence<T> {
+    pub alpha: fn(usize) -> T,
+    pub beta: fn(usize) -> T,
+    pub gamma: fn(usize) -> T,
+}
+
+impl<T: Coefficient> Recurrence<T> {
+    pub fn mulx(&self, c: &[T]) -> Vec<T> {
+        let mut product = vec![T::zero(); c.len() + 1];
+        for (n, coefficient) in c.iter().enumerate() {
+            product[n + 1] = product[n + 1].clone() + (self.alpha)(n) * coefficient.clone();
+            product[n] = product[n].clone() + (self.beta)(n) * coefficient.clone();
+            if n > 0 {
+                product[n - 1] = product[n - 1].clone() + (self.gamma)(n) * coefficient.clone();
+            }
+        }
+        product
+    }
+
+    /// c1 * c2 as sum_n c1_n (P_n c2), where each P_n c2 is found from the
+    /// two before it by the recurrence
+    pub fn mul(&self, c1: &[T], c2: &[T]) -> Vec<T> {
+        if c1.is_empty() || c2.is_empty() {
+            return Vec::new()
+        }
+        let mut product = vec![T::zero(); c1.len() + c2.len() - 1];
+        let mut previous: Vec<T> = Vec::new();
+        let mut current = c2.to_vec();
+        for (n, coefficient) in c1.iter().enumerate() {
+            product = add(&product, &scale(&current, coefficient.clone()));
+            // P_(n+1) c2 = ((x - beta_n) P_n c2 - gamma_n P_(n-1) c2) / alpha_n
+            let mut next = sub(&self.mulx(&current), &scale(&current, (self.beta)(n)));
+            next = sub(&next, &scale(&previous, (self.gamma)(n)));
+            next = scale(&next, T::one() / (self.alpha)(n));
+            previous = current;
+            current = next;
+        }
+        trim(product)
+    }
+
+    /// evaluates sum_n c_n P_n(x) with Clenshaw's algorithm
+    pub fn val(&self, c: &[T], x: T) -> T {
+        // P_(n+1) = a_n P_n + b_n P_(n-1), with
+        let a = |n: usize| (x.clone() - (self.beta)(n)) / (self.alpha)(n);
+        let b = |n: usize| (T::zero() - (self.gamma)(n)) / (self.alpha)(n);
+        let mut next = T::zero();
+        let mut next_next = T::zero();
+        for n in (0..c.len()).rev() {
+            let current = c[n].clone() + a(n) * next.clone() + b(n + 1) * next_next;
+            next_next = next;
+            next = current;
+        }
+        next
+    }
+
+    /// the power-basis coefficients of sum_n c_n P_n
+    pub fn to_power(&self, c: &[T]) -> Vec<T> {
+        let mut poly = vec![T::zero(); c.len()];
+        let mut previous: Vec<T> = Vec::new();
+        let mut current = vec![T::one()];
+        for (n, coefficient) in c.iter().enumerate() {
+            poly = add(&poly, &scale(&current, coefficient.clone()));
+            let mut next = sub(&shift(&current), &scale(&current, (self.beta)(n)));
+            next = sub(&next, &scale(&previous, (self.gamma)(n)));
+            next = scale(&next, T::one() / (self.alpha)(n));
+            previous = current;
+            current = next;
+        }
+        trim(poly)
+    }
+
+    /// the basis coefficients of a polynomial given in the power basis, by
+    /// Horner's rule with multiplication by x done in the basis
+    pub fn power_to_basis(&self, p: &[T]) -> Vec<T> {
+        let mut c: Vec<T> = Vec::new();
+        for coefficient in p.iter().rev() {
+            c = add(&self.mulx(&c), std::slice::from_ref(coefficient));
+        }
+        trim(c)
+    }
+
+    pub fn der(&self, c: &[T]) -> Vec<T> {
+        let poly = self.to_power(c);
+        let derivative = poly
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, coefficient)| T::from_usize(i).unwrap() * coefficient.clone())
+            .collect::<Vec<_>>();
+        self.power_to_basis(&derivative)
+    }
+
+    /// the integral whose value at 0 is k
+    pub fn int(&self, c: &[T], k: T) -> Vec<T> {
+        let poly = self.to_power(c);
+        let mut integral = vec![k];
+        integral.extend(poly
+            .iter()
+            .enumerate()
+            .map(|(i, coefficient)| coefficient.clone() / T::from_usize(i + 1).unwrap()));
+        self.power_to_basis(&integral)
+    }
+
+    /// the quotient and remainder of c1 / c2, which do not depend on the basis
+    pub fn div(&self, c1: &[T], c2: &[T]) -> (Vec<T>, Vec<T>) {
+        let (quotient, remainder) = power_div(&self.to_power(c1), &self.to_power(c2));
+        (self.power_to_basis(&quotient), self.power_to_basis(&remainder))
+    }
+}
+
+pub(crate) fn add<T: Coefficient>(c1: &[T], c2: &[T]) -> Vec<T> {
+    let mut sum = vec![T::zero(); c1.len().max(c2.len())];
+    for (i, coefficient) in c1.iter().enumerate() {
+        sum[i] = sum[i].clone() + coefficient.clone();
+    }
+    for (i, coefficient) in c2.iter().enumerate() {
+        sum[i] = sum[i].clone() + coefficient.clone();
+    }
+    sum
+}
+
+pub(crate) fn sub<T: Coefficient>(c1: &[T], c2: &[T]) -> Vec<T> {
+    add(c1, &scale(c2, T::zero() - T::one()))
+}
+
+fn scale<T: Coefficient>(c: &[T], factor: T) -> Vec<T> {
+    c.iter().map(|x| x.clone() * factor.clone()).collect()
+}
+
+/// the product of two polynomials in the power basis
+pub(crate) fn multiply<T: Coefficient>(p1: &[T], p2: &[T]) -> Vec<T> {
+    if p1.is_empty() || p2.is_empty() {
+        return Vec::new()
+    }
+    let mut product = vec![T::zero(); p1.len() + p2.len() - 1];
+    for (i, x) in p1.iter().enumerate() {
+        for (j, y) in p2.iter().enumerate() {
+            product[i + j] = product[i + j].clone() + x.clone() * y.clone();
+        }
+    }
+    product
+}
+
+/// multiplies power-basis coefficients by x
+fn shift<T: Coefficient>(p: &[T]) -> Vec<T> {
+    let mut shifted = vec![T::zero()];
+    shifted.extend_from_slice(p);
+    shifted
+}
+
+pub(crate) fn trim<T: Coefficient>(mut c: Vec<T>) -> Vec<T> {
+    while c.last().is_some_and(|x| x.is_zero()) {
+        c.pop();
+    }
+    c
+}
+
+/// long division in the power basis, which stays in the integers for a
+/// monic divisor
+pub(crate) fn power_div<T: Coefficient>(p1: &[T], p2: &[T]) -> (Vec<T>, Vec<T>) {
+    assert!(!p2.is_empty(), "division by the zero polynomial");
+    if p1.len() < p2.len() {
+        return (Vec::new(), p1.to_vec())
+    }
+    let mut remainder = p1.to_vec();
+    let mut quotient = vec![T::zero(); p1.len() - p2.len() + 1];
+    let lead = p2[p2.len() - 1].clone();
+    for i in (0..quotient.len()).rev() {
+        let factor = remainder[i + p2.len() - 1].clone() / lead.clone();
+        for (j, coefficient) in p2.iter().enumerate() {
+            remainder[i + j] = remainder[i + j].clone() - factor.clone() * coefficient.clone();
+        }
+        quotient[i] = factor;
+    }
+    remainder.truncate(p2.len() - 1);
+    (quotient, trim(remainder))
+}
+
+/// compares coefficients to a relative tolerance, reading missing ones as 0
+#[cfg(test)]
+pub(crate) fn assert_close(p: &polynomial::Polynomial<f32>, expected: &[f32]) {
+    let length = p.data().len().max(expected.len());
+    for i in 0..length {
+        let x = p.data().get(i).copied().unwrap_or(0.0);
+        let y = expected.get(i).copied().unwrap_or(0.0);
+        assert!((x - y).abs() <= 1e-4 * y.abs().max(1.0), "{:?} {:?}", p.data(), expected);
+    }
+}
@@ -0,0 +1,163 @@
+use polynomial::Polynomial;
+use crate::polynomials::basis::{Coefficient, Recurrence, ratio, add, sub};
+
+// Following numpy.polynomial.chebyshev, the Chebyshev polynomials of the
+// first kind T_n, and alongside them, with a `chebu` prefix, those of the
+// second kind U_n, which numpy does not have. T_n(cos t) = cos(nt) and
+// U_n(cos t) sin t = sin((n + 1)t).
+
+/// x T_0 = T_1, and x T_n = T_(n+1) / 2 + T_(n-1) / 2 after that
+pub(crate) fn chebyshev_t<T: Coefficient>() -> Recurrence<T> {
+    Recurrence {
+        alpha: |n| if n == 0 { T::one() } else { ratio(1, 2) },
+        beta: |_| T::zero(),
+        gamma: |_| ratio(1, 2),
+    }
+}
+
+/// x U_n = U_(n+1) / 2 + U_(n-1) / 2
+fn chebyshev_u<T: Coefficient>() -> Recurrence<T> {
+    Recurrence {
+        alpha: |_| ratio(1, 2),
+        beta: |_| T::zero(),
+        gamma: |_| ratio(1, 2),
+    }
+}
+
+pub fn chebadd(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(add(p.data(), q.data()))
+}
+
+pub fn chebsub(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(sub(p.data(), q.data()))
+}
+
+pub fn chebmulx(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(chebyshev_t::<f32>().mulx(p.data()))
+}
+
+pub fn chebmul(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(chebyshev_t::<f32>().mul(p.data(), q.data()))
+}
+
+/// The quotient and remainder of p / q.
+pub fn chebdiv(p: &Polynomial<f32>, q: &Polynomial<f32>) -> (Polynomial<f32>, Polynomial<f32>) {
+    let (quotient, remainder) = chebyshev_t::<f32>().div(p.data(), q.data());
+    (Polynomial::new(quotient), Polynomial::new(remainder))
+}
+
+/// Evaluates the Chebyshev series p at x, by Clenshaw's algorithm.
+pub fn chebval(x: f32, p: &Polynomial<f32>) -> f32 {
+    chebyshev_t::<f32>().val(p.data(), x)
+}
+
+pub fn chebder(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(chebyshev_t::<f32>().der(p.data()))
+}
+
+/// The integral of p whose value at 0 is k.
+pub fn chebint(p: &Polynomial<f32>, k: f32) -> Polynomial<f32> {
+    Polynomial::new(chebyshev_t::<f32>().int(p.data(), k))
+}
+
+pub fn poly2cheb(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(chebyshev_t::<f32>().power_to_basis(p.data()))
+}
+
+pub fn cheb2poly(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(chebyshev_t::<f32>().to_power(p.data()))
+}
+
+pub fn chebuadd(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(add(p.data(), q.data()))
+}
+
+pub fn chebusub(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(sub(p.data(), q.data()))
+}
+
+pub fn chebumulx(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(chebyshev_u::<f32>().mulx(p.data()))
+}
+
+pub fn chebumul(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(chebyshev_u::<f32>().mul(p.data(), q.data()))
+}
+
+/// The quotient and remainder of p / q.
+pub fn chebudiv(p: &Polynomial<f32>, q: &Polynomial<f32>) -> (Polynomial<f32>, Polynomial<f32>) {
+    let (quotient, remainder) = chebyshev_u::<f32>().div(p.data(), q.data());
+    (Polynomial::new(quotient), Polynomial::new(remainder))
+}
+
+/// Evaluates the series p in the U_n at x, by Clenshaw's algorithm.
+pub fn chebuval(x: f32, p: &Polynomial<f32>) -> f32 {
+    chebyshev_u::<f32>().val(p.data(), x)
+}
+
+pub fn chebuder(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(chebyshev_u::<f32>().der(p.data()))
+}
+
+/// The integral of p whose value at 0 is k.
+pub fn chebuint(p: &Polynomial<f32>, k: f32) -> Polynomial<f32> {
+    Polynomial::new(chebyshev_u::<f32>().int(p.data(), k))
+}
+
+pub fn poly2chebu(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(chebyshev_u::<f32>().power_to_basis(p.data()))
+}
+
+pub fn chebu2poly(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(chebyshev_u::<f32>().to_power(p.data()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::basis::assert_close;
+
+    #[test]
+    fn test_chebyshev_numpy() {
+        // the examples from numpy.polynomial.chebyshev
+        let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+        let q = Polynomial::new(vec![3.0, 2.0, 1.0]);
+        assert_close(&chebadd(&p, &q), &[4.0, 4.0, 4.0]);
+        assert_close(&chebsub(&p, &q), &[-2.0, 0.0, 2.0]);
+        assert_close(&chebmulx(&p), &[1.0, 2.5, 1.0, 1.5]);
+        assert_close(&chebmul(&p, &q), &[6.5, 12.0, 12.0, 4.0, 1.5]);
+        let (quotient, remainder) = chebdiv(&p, &q);
+        assert_close(&quotient, &[3.0]);
+        assert_close(&remainder, &[-8.0, -4.0]);
+        assert_eq!(chebval(0.5, &p), 0.5);
+        assert_close(&chebder(&Polynomial::new(vec![1.0, 2.0, 3.0, 4.0])), &[14.0, 12.0, 24.0]);
+        assert_close(&chebint(&p, 0.0), &[0.5, -0.5, 0.5, 0.5]);
+        assert_close(&poly2cheb(&Polynomial::new(vec![0.0, 1.0, 2.0, 3.0])), &[1.0, 3.25, 1.0, 0.75]);
+        assert_close(&cheb2poly(&Polynomial::new(vec![0.0, 1.0, 2.0, 3.0])), &[-2.0, -8.0, 4.0, 12.0]);
+    }
+
+    #[test]
+    fn test_chebyshev_u() {
+        // U_2 = 4x^2 - 1 and U_3 = 8x^3 - 4x
+        assert_close(&chebu2poly(&Polynomial::new(vec![0.0, 0.0, 1.0])), &[-1.0, 0.0, 4.0]);
+        assert_close(&poly2chebu(&Polynomial::new(vec![0.0, -4.0, 0.0, 8.0])), &[0.0, 0.0, 0.0, 1.0]);
+        // U_1 U_1 = U_0 + U_2, and U_1 U_2 = U_1 + U_3
+        let u_1 = Polynomial::new(vec![0.0, 1.0]);
+        assert_close(&chebumul(&u_1, &u_1), &[1.0, 0.0, 1.0]);
+        assert_close(&chebumul(&u_1, &Polynomial::new(vec![0.0, 0.0, 1.0])), &[0.0, 1.0, 0.0, 1.0]);
+        assert_close(&chebumulx(&Polynomial::new(vec![1.0, 2.0])), &[1.0, 0.5, 1.0]);
+        let (quotient, remainder) = chebudiv(&Polynomial::new(vec![1.0, 1.0, 1.0]), &u_1);
+        assert_close(&quotient, &[1.0, 1.0]);
+        assert_close(&remainder, &[0.0]);
+        // U_3(1) = 4, and the U_n(cos t) sin t = sin((n + 1)t)
+        assert_eq!(chebuval(1.0, &Polynomial::new(vec![0.0, 0.0, 0.0, 1.0])), 4.0);
+        let t = 0.3f32;
+        let value = chebuval(t.cos(), &Polynomial::new(vec![0.0, 0.0, 0.0, 0.0, 1.0]));
+        assert!((value * t.sin() - (5.0 * t).sin()).abs() < 1e-5);
+        // U_3' = 24x^2 - 4 = 6 U_2 + 2 U_0, and integrating gives U_3 back
+        let derivative = chebuder(&Polynomial::new(vec![0.0, 0.0, 0.0, 1.0]));
+        assert_close(&derivative, &[2.0, 0.0, 6.0]);
+        assert_close(&chebuint(&derivative, 0.0), &[0.0, 0.0, 0.0, 1.0]);
+        assert_close(&chebusub(&chebuadd(&u_1, &u_1), &u_1), &[0.0, 1.0]);
+    }
+}
@@ -0,0 +1,103 @@
+use num::{BigInt, BigUint, Zero};
+use crate::polynomials::complete_matching_counts;
+
+/// The matching polynomial of the complement of a graph G on n nodes,
+/// straight from that of G, with no graph and no recursion. The
+/// coefficients are signless and indexed by the power of x, as the engines
+/// return them; only the first n + 1 are read.
+///
+/// By Godsil's identity, the signed matching polynomial is
+///     m(G) = sum_k p(complement of G, k) He_(n - 2k)
+/// so the coefficients of m(G) in the Hermite basis are the numbers of
+/// matchings of the complement. Expanding each power of x with
+///     x^m = sum_j m! / (2^j j! (m - 2j)!) He_(m - 2j)
+/// gives them exactly, in integers, where going through `poly2herme` would
+/// work in f32.
+pub fn complement_polynomial(coefficients: &[u64], n: usize) -> Vec<BigUint> {
+    let coefficients = coefficients
+        .iter()
+        .map(|x| BigUint::from(*x))
+        .collect::<Vec<_>>();
+    complement_transform(&coefficients, n)
+}
+
+/// The inverse of `complement_polynomial`: the matching polynomial of G from
+/// that of its complement. As the complement of the complement is G again,
+/// this is the same transform, here taking big coefficients so that the
+/// output of `complement_polynomial` can be passed straight back.
+pub fn complement_polynomial_inverse(coefficients: &[BigUint], n: usize) -> Vec<BigUint> {
+    complement_transform(coefficients, n)
+}
+
+fn complement_transform(coefficients: &[BigUint], n: usize) -> Vec<BigUint> {
+    assert!(coefficients.len() > n, "a graph on {} nodes has {} coefficients", n, n + 1);
+    let counts = complete_matching_counts::<BigInt>(n);
+    let mut complement = vec![BigUint::zero(); n + 1];
+    for l in 0..=n / 2 {
+        // the coefficient of He_(n - 2l) in the signed m(G)
+        let mut hermite_coefficient = BigInt::zero();
+        for k in 0..=l {
+            let term = BigInt::from(coefficients[n - 2 * k].clone()) * &counts[n - 2 * k][l - k];
+            if k % 2 == 0 {
+                hermite_coefficient += term;
+            } else {
+                hermite_coefficient -= term;
+            }
+        }
+        complement[n - 2 * l] = hermite_coefficient
+            .to_biguint()
+            .expect("the coefficients should be those of a matching polynomial on n nodes");
+    }
+    complement
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_graph_matching::{BinaryGraph, calculate_matching_polynomial_pointer};
+    use crate::traits::Graph;
+    use num::One;
+
+    #[test]
+    fn test_complement_polynomial() {
+        let standard_data = [
+            0b11001, 0b1001, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        let mut chain_data = [0; 64];
+        for (node, row) in chain_data.iter_mut().enumerate().take(9) {
+            *row = if node < 8 { 0b11 << (7 - node) } else { 1 };
+        }
+        for (data, n) in [(standard_data, 5), (chain_data, 9)] {
+            let graph = BinaryGraph::from(data);
+            let matching_poly = calculate_matching_polynomial_pointer(graph);
+            let complement_poly = calculate_matching_polynomial_pointer(graph.complement());
+            let expected = complement_poly[..=n]
+                .iter()
+                .map(|x| BigUint::from(*x))
+                .collect::<Vec<_>>();
+            let transformed = complement_polynomial(&matching_poly, n);
+            assert_eq!(transformed, expected);
+            let original = matching_poly[..=n]
+                .iter()
+                .map(|x| BigUint::from(*x))
+                .collect::<Vec<_>>();
+            assert_eq!(complement_polynomial_inverse(&transformed, n), original);
+        }
+
+        // the complement of K_30 is edgeless, though m(K_30) is far beyond
+        // the precision of f32
+        let n = 30;
+        let counts = complete_matching_counts::<BigInt>(n);
+        let mut complete_poly = vec![BigUint::zero(); n + 1];
+        for (j, count) in counts[n].iter().enumerate() {
+            complete_poly[n - 2 * j] = count.to_biguint().unwrap();
+        }
+        let mut edgeless_poly = vec![BigUint::zero(); n + 1];
+        edgeless_poly[n] = BigUint::one();
+        assert_eq!(complement_polynomial_inverse(&complete_poly, n), edgeless_poly);
+        assert_eq!(complement_polynomial_inverse(&edgeless_poly, n), complete_poly);
+    }
+}
@@ -0,0 +1,191 @@
+use num::{BigInt, BigRational, Num, FromPrimitive, Zero, One};
+use crate::polynomials::basis::{Recurrence, ratio, trim};
+use crate::polynomials::hermite::hermite_e;
+use crate::polynomials::chebyshev::chebyshev_t;
+
+// Exact versions of the basis conversions, for matching polynomials that
+// come out of the engines with integer coefficients. Three bases:
+//     Hermite-E, He_n, in which m(G) = sum_k p(complement of G, k) He_(n - 2k)
+//         and which carries Godsil's expected matching polynomial;
+//     Chebyshev, T_n, into which m(G) is expanded at x / 2, so that the
+//         cycle C_n contributes 2 T_n(x / 2);
+//     the falling factorials x^(n) = x (x - 1) ... (x - n + 1).
+// Each satisfies a three-term recurrence, so these use the machinery of
+// `basis.rs` with BigRational coefficients in place of f32, so that nothing is
+// lost to rounding.
+
+/// x x^(n) = x^(n+1) + n x^(n)
+fn falling_factorial() -> Recurrence<BigRational> {
+    Recurrence {
+        alpha: |_| BigRational::one(),
+        beta: |n| ratio(n as i64, 1),
+        gamma: |_| BigRational::zero(),
+    }
+}
+
+/// p(factor x)
+fn rescale(p: &[BigRational], factor: &BigRational) -> Vec<BigRational> {
+    let mut power = BigRational::one();
+    p.iter()
+        .map(|coefficient| {
+            let term = coefficient * &power;
+            power *= factor;
+            term
+        })
+        .collect()
+}
+
+/// The signed matching polynomial, in the power basis, of the signless
+/// coefficients that the engines return for a graph on n nodes, in whichever
+/// number type the caller works with.
+pub fn signed_matching_polynomial<T: Num + FromPrimitive>(coefficients: &[u64], n: usize) -> Vec<T> {
+    assert!(coefficients.len() > n, "a graph on {} nodes has {} coefficients", n, n + 1);
+    (0..=n)
+        .map(|i| {
+            let coefficient = T::from_u64(coefficients[i]).unwrap();
+            match ((n - i) / 2).is_multiple_of(2) {
+                true => coefficient,
+                false => T::zero() - coefficient,
+            }
+        })
+        .collect()
+}
+
+pub fn poly2herme_exact(p: &[BigRational]) -> Vec<BigRational> {
+    hermite_e().power_to_basis(p)
+}
+
+pub fn herme2poly_exact(c: &[BigRational]) -> Vec<BigRational> {
+    hermite_e().to_power(c)
+}
+
+pub fn poly2cheb_exact(p: &[BigRational]) -> Vec<BigRational> {
+    chebyshev_t().power_to_basis(p)
+}
+
+pub fn cheb2poly_exact(c: &[BigRational]) -> Vec<BigRational> {
+    chebyshev_t().to_power(c)
+}
+
+pub fn poly2falling(p: &[BigRational]) -> Vec<BigRational> {
+    falling_factorial().power_to_basis(p)
+}
+
+pub fn falling2poly(c: &[BigRational]) -> Vec<BigRational> {
+    falling_factorial().to_power(c)
+}
+
+/// The coefficients of m(G) in the He_n, from the signless coefficients of
+/// an engine. Coefficient n - 2k is the number of k-matchings of the
+/// complement of G, so these are always non-negative integers.
+pub fn matching_polynomial_to_herme(coefficients: &[u64], n: usize) -> Vec<BigRational> {
+    poly2herme_exact(&signed_matching_polynomial(coefficients, n))
+}
+
+/// The signed matching polynomial in the power basis from its He_n
+/// coefficients.
+pub fn matching_polynomial_from_herme(c: &[BigRational]) -> Vec<BigRational> {
+    herme2poly_exact(c)
+}
+
+/// The c_k with m(G, x) = sum_k c_k T_k(x / 2), from the signless
+/// coefficients of an engine. For the cycle C_n this is 2 at k = n and 0
+/// elsewhere.
+pub fn matching_polynomial_to_cheb(coefficients: &[u64], n: usize) -> Vec<BigRational> {
+    let two = BigRational::from_integer(BigInt::from(2));
+    poly2cheb_exact(&rescale(&signed_matching_polynomial(coefficients, n), &two))
+}
+
+/// The signed matching polynomial in the power basis from the c_k of
+/// `matching_polynomial_to_cheb`.
+pub fn matching_polynomial_from_cheb(c: &[BigRational]) -> Vec<BigRational> {
+    let half = BigRational::new(BigInt::one(), BigInt::from(2));
+    trim(rescale(&cheb2poly_exact(c), &half))
+}
+
+/// The coefficients of m(G) in the falling factorials, from the signless
+/// coefficients of an engine.
+pub fn matching_polynomial_to_falling_factorial(coefficients: &[u64], n: usize) -> Vec<BigRational> {
+    poly2falling(&signed_matching_polynomial(coefficients, n))
+}
+
+/// The signed matching polynomial in the power basis from its falling
+/// factorial coefficients.
+pub fn matching_polynomial_from_falling_factorial(c: &[BigRational]) -> Vec<BigRational> {
+    falling2poly(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_graph_matching::calculate_matching_polynomial_pointer;
+    use crate::graph_operations::graph_from_edges;
+    use crate::traits::Graph;
+    use crate::polynomials::herme_table;
+
+    fn integers(values: &[i64]) -> Vec<BigRational> {
+        values.iter().map(|x| BigRational::from_integer(BigInt::from(*x))).collect()
+    }
+
+    #[test]
+    fn test_matching_polynomial_expansions() {
+        let n = 7;
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 0), (3, 4), (4, 5), (5, 6), (2, 5)];
+        let graph = graph_from_edges(n, &edges);
+        let matching_poly = calculate_matching_polynomial_pointer(graph);
+        let signed = signed_matching_polynomial::<BigRational>(&matching_poly, n);
+
+        // the He_n coefficients count the matchings of the complement
+        let complement_poly = calculate_matching_polynomial_pointer(graph.complement());
+        let herme = matching_polynomial_to_herme(&matching_poly, n);
+        for k in 0..=n / 2 {
+            assert_eq!(herme[n - 2 * k], BigRational::from_integer(BigInt::from(complement_poly[n - 2 * k])));
+        }
+        assert_eq!(matching_polynomial_from_herme(&herme), signed);
+
+        let cheb = matching_polynomial_to_cheb(&matching_poly, n);
+        assert_eq!(matching_polynomial_from_cheb(&cheb), signed);
+        let falling = matching_polynomial_to_falling_factorial(&matching_poly, n);
+        assert_eq!(matching_polynomial_from_falling_factorial(&falling), signed);
+
+        // cycles are 2 T_n(x / 2)
+        for n in 3..10 {
+            let mut cycle_edges = (1..n).map(|v| (v - 1, v)).collect::<Vec<_>>();
+            cycle_edges.push((0, n - 1));
+            let cycle_poly = calculate_matching_polynomial_pointer(graph_from_edges(n, &cycle_edges));
+            let mut expected = integers(&vec![0; n]);
+            expected.push(BigRational::from_integer(BigInt::from(2)));
+            assert_eq!(matching_polynomial_to_cheb(&cycle_poly, n), expected);
+        }
+
+        // m(K_3) = x^3 - 3x = x^(3) + 3 x^(2) - 2 x^(1)
+        let triangle_poly = calculate_matching_polynomial_pointer(graph_from_edges(3, &[(0, 1), (1, 2), (0, 2)]));
+        assert_eq!(matching_polynomial_to_falling_factorial(&triangle_poly, 3), integers(&[0, -2, 3, 1]));
+        assert_eq!(matching_polynomial_to_herme(&triangle_poly, 3), integers(&[0, 0, 0, 1]));
+    }
+
+    #[test]
+    fn test_exact_conversions() {
+        // the numpy examples for chebyshev, now exactly
+        let p = integers(&[0, 1, 2, 3]);
+        let half = |x: i64| BigRational::new(BigInt::from(x), BigInt::from(4));
+        assert_eq!(poly2cheb_exact(&p), vec![half(4), half(13), half(4), half(3)]);
+        assert_eq!(cheb2poly_exact(&p), integers(&[-2, -8, 4, 12]));
+        assert_eq!(herme2poly_exact(&integers(&[2, 10, 2, 3])), p);
+        assert_eq!(poly2herme_exact(&p), integers(&[2, 10, 2, 3]));
+        // x^3 = x^(3) + 3 x^(2) + x^(1), the Stirling numbers of the second kind
+        assert_eq!(poly2falling(&integers(&[0, 0, 0, 1])), integers(&[0, 1, 3, 1]));
+        assert_eq!(falling2poly(&integers(&[0, 1, 3, 1])), integers(&[0, 0, 0, 1]));
+        assert!(poly2falling(&[]).is_empty());
+
+        // He_n agrees with the integer table of the hermite module
+        let size = 21;
+        let table = herme_table(size);
+        for n in 0..size {
+            let mut basis_element = integers(&vec![0; n]);
+            basis_element.push(BigRational::one());
+            let expected = (0..=n).map(|i| table[n * size + i]).collect::<Vec<_>>();
+            assert_eq!(herme2poly_exact(&basis_element), integers(&expected));
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use polynomial::Polynomial;
+use crate::polynomials::{herme2poly, chebu2poly, cheb2poly, lag2poly};
+
+// The matching polynomials of the standard families are single members (or
+// short sums) of the orthogonal bases, up to a change of variable:
+//     m(K_n, x) = He_n(x)
+//     m(P_n, x) = U_n(x / 2)
+//     m(C_n, x) = 2 T_n(x / 2)
+//     m(K_(m,n), x) = (-1)^m m! x^(n - m) L_m^(n - m)(x^2)  for m <= n
+// The functions below build them that way, signed and in the power basis.
+
+/// e_n, the basis element of degree n
+fn basis_element(n: usize) -> Polynomial<f32> {
+    let mut coeffics = vec![0.0; n];
+    coeffics.push(1.0);
+    Polynomial::new(coeffics)
+}
+
+/// p(x / 2)
+fn halve_variable(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(p
+        .data()
+        .iter()
+        .enumerate()
+        .map(|(i, coefficient)| coefficient / 2f32.powi(i as i32))
+        .collect())
+}
+
+/// The matching polynomial of the complete graph K_n, the Hermite
+/// polynomial He_n.
+pub fn complete_graph_matching_polynomial(n: usize) -> Polynomial<f32> {
+    herme2poly(&basis_element(n))
+}
+
+/// The matching polynomial of the path on n nodes, U_n(x / 2).
+pub fn path_matching_polynomial(n: usize) -> Polynomial<f32> {
+    halve_variable(&chebu2poly(&basis_element(n)))
+}
+
+/// The matching polynomial of the cycle on n >= 3 nodes, 2 T_n(x / 2).
+pub fn cycle_matching_polynomial(n: usize) -> Polynomial<f32> {
+    assert!(n >= 3, "a cycle has at least three nodes");
+    halve_variable(&cheb2poly(&basis_element(n))) * Polynomial::new(vec![2.0])
+}
+
+/// The matching polynomial of the complete bipartite graph K_(m,n). The
+/// generalised Laguerre polynomial it needs is, for alpha = n - m,
+///     L_m^alpha = sum_i C(alpha + m - i - 1, m - i) L_i
+/// which is what we expand in the Laguerre basis.
+pub fn complete_bipartite_matching_polynomial(m: usize, n: usize) -> Polynomial<f32> {
+    let (m, n) = if m <= n { (m, n) } else { (n, m) };
+    let alpha = n - m;
+    let laguerre_coeffics = (0..=m)
+        .map(|i| match alpha {
+            0 => if i == m { 1.0 } else { 0.0 },
+            _ => binomial(alpha + m - i - 1, m - i),
+        })
+        .collect::<Vec<_>>();
+    let generalised = lag2poly(&Polynomial::new(laguerre_coeffics));
+
+    // substitute x^2, multiply by x^(n - m), and scale by (-1)^m m!
+    let factorial = (1..=m).map(|i| i as f32).product::<f32>();
+    let sign = if m.is_multiple_of(2) { 1.0 } else { -1.0 };
+    let mut coeffics = vec![0.0; alpha + 2 * generalised.data().len()];
+    for (i, coefficient) in generalised.data().iter().enumerate() {
+        coeffics[alpha + 2 * i] = sign * factorial * coefficient;
+    }
+    Polynomial::new(coeffics)
+}
+
+fn binomial(n: usize, k: usize) -> f32 {
+    (0..k).fold(1.0, |product, i| product * (n - i) as f32 / (i + 1) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_graph_matching::calculate_matching_polynomial_pointer;
+    use crate::graph_operations::graph_from_edges;
+    use crate::polynomials::basis::assert_close;
+    use crate::polynomials::expansions;
+
+    /// the signed matching polynomial of a graph from the pointer engine
+    fn signed_matching_polynomial(n: usize, edges: &[(usize, usize)]) -> Vec<f32> {
+        let matching_poly = calculate_matching_polynomial_pointer(graph_from_edges(n, edges));
+        expansions::signed_matching_polynomial::<f32>(&matching_poly, n)
+    }
+
+    #[test]
+    fn test_graph_families() {
+        for n in [3, 6, 9] {
+            let complete_edges = (0..n).flat_map(|u| (u + 1..n).map(move |v| (u, v))).collect::<Vec<_>>();
+            assert_close(&complete_graph_matching_polynomial(n), &signed_matching_polynomial(n, &complete_edges));
+            let path_edges = (1..n).map(|v| (v - 1, v)).collect::<Vec<_>>();
+            assert_close(&path_matching_polynomial(n), &signed_matching_polynomial(n, &path_edges));
+            let mut cycle_edges = path_edges.clone();
+            cycle_edges.push((0, n - 1));
+            assert_close(&cycle_matching_polynomial(n), &signed_matching_polynomial(n, &cycle_edges));
+        }
+        for (m, n) in [(1, 1), (2, 5), (4, 4), (5, 3)] {
+            let edges = (0..m).flat_map(|u| (0..n).map(move |v| (u, m + v))).collect::<Vec<_>>();
+            assert_close(&complete_bipartite_matching_polynomial(m, n), &signed_matching_polynomial(m + n, &edges));
+        }
+    }
+}
@@ -2,6 +2,8 @@ use polynomial::Polynomial;
 //use std::cmp::PartialEq;
 //use std::ops::{Mul, Div};
 use std::cmp::min;
+use std::num::Wrapping;
+use crate::polynomials::basis::{Coefficient, Recurrence, ratio, sub};
 
 // Following the numpy way of doing things, we implement in this file some
 // conversions between Hermite polynomials and standard basis polynomials
@@ -96,7 +98,143 @@ pub fn herme2poly(p: &Polynomial<f32>) -> Polynomial<f32> {
     }
 }
 
+/// The coefficients of the probabilists' Hermite polynomials He_0 up to
+/// He_(size - 1) in the standard basis, row-major with He_n in row n. The
+/// coefficient of x^(n - 2k) in He_n is (-1)^k times the number of
+/// k-matchings of K_n, from `complete_matching_counts`.
+/// Unlike going through `herme2poly` in f32, this is exact: the arithmetic
+/// wraps, so every entry is right modulo 2^64, and any integer combination of
+/// the entries that fits in an i64 comes out exactly.
+pub fn herme_table(size: usize) -> Vec<i64> {
+    let mut table = vec![0i64; size * size];
+    if size == 0 {
+        return table
+    }
+    let counts = complete_matching_counts::<Wrapping<i64>>(size - 1);
+    for (n, row) in counts.iter().enumerate() {
+        for (k, count) in row.iter().enumerate() {
+            table[n * size + n - 2 * k] = match k.is_multiple_of(2) {
+                true => count.0,
+                false => count.0.wrapping_neg(),
+            };
+        }
+    }
+    table
+}
+
+/// Entry k of row m is the number of k-matchings of the complete graph K_m,
+///     m! / (2^k k! (m - 2k)!)
+/// for m up to n, in whichever number type the caller needs. Rows are built
+/// with T(m, k) = T(m - 1, k) + (m - 1) T(m - 2, k - 1), leaving the last
+/// node out or pairing it with one of the others.
+pub(crate) fn complete_matching_counts<T: Coefficient>(n: usize) -> Vec<Vec<T>> {
+    let mut counts: Vec<Vec<T>> = Vec::with_capacity(n + 1);
+    for m in 0..=n {
+        let row = (0..=m / 2)
+            .map(|k| {
+                let unmatched = match m {
+                    0 => T::one(),
+                    _ => counts[m - 1].get(k).cloned().unwrap_or_else(T::zero),
+                };
+                let matched = match m >= 2 && k >= 1 {
+                    true => counts[m - 2][k - 1].clone() * T::from_usize(m - 1).unwrap(),
+                    false => T::zero(),
+                };
+                unmatched + matched
+            })
+            .collect();
+        counts.push(row);
+    }
+    counts
+}
+
+/// x He_n = He_(n+1) + n He_(n-1)
+pub(crate) fn hermite_e<T: Coefficient>() -> Recurrence<T> {
+    Recurrence {
+        alpha: |_| T::one(),
+        beta: |_| T::zero(),
+        gamma: |n| ratio(n as i64, 1),
+    }
+}
+
+pub fn hermesub(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(sub(p.data(), q.data()))
+}
+
+pub fn hermemul(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(hermite_e::<f32>().mul(p.data(), q.data()))
+}
+
+/// The quotient and remainder of p / q.
+pub fn hermediv(p: &Polynomial<f32>, q: &Polynomial<f32>) -> (Polynomial<f32>, Polynomial<f32>) {
+    let (quotient, remainder) = hermite_e::<f32>().div(p.data(), q.data());
+    (Polynomial::new(quotient), Polynomial::new(remainder))
+}
+
+/// Evaluates the Hermite series p at x, by Clenshaw's algorithm.
+pub fn hermeval(x: f32, p: &Polynomial<f32>) -> f32 {
+    hermite_e::<f32>().val(p.data(), x)
+}
+
+pub fn hermeder(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(hermite_e::<f32>().der(p.data()))
+}
+
+/// The integral of p whose value at 0 is k.
+pub fn hermeint(p: &Polynomial<f32>, k: f32) -> Polynomial<f32> {
+    Polynomial::new(hermite_e::<f32>().int(p.data(), k))
+}
+
 /// Multiply a polynomial by x
 fn polymulx(p: &Polynomial<f32>) -> Polynomial<f32> {
     p * Polynomial::new(vec![0.0, 1.0])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::basis::assert_close;
+
+    #[test]
+    fn test_herme_table() {
+        let size = 31;
+        let table = herme_table(size);
+        // agrees with the f32 conversion while that is still exact
+        for n in 0..10 {
+            let mut coeffics = vec![0.0; n];
+            coeffics.push(1.0);
+            let hermite = herme2poly(&Polynomial::new(coeffics));
+            for (i, coefficient) in hermite.data().iter().enumerate() {
+                assert_eq!(table[n * size + i], *coefficient as i64);
+            }
+        }
+        // He_30(0) = (-1)^15 29!!, which f32 cannot hold
+        assert_eq!(table[30 * size], -6190283353629375);
+        assert_eq!(table[30 * size + 30], 1);
+        assert_eq!(table[30 * size + 28], -435);
+
+        // K_4 has 6 edges and 3 perfect matchings
+        assert_eq!(complete_matching_counts::<u64>(4)[4], vec![1, 6, 3]);
+        assert_eq!(complete_matching_counts::<f64>(0), vec![vec![1.0]]);
+    }
+
+    #[test]
+    fn test_hermite_e_numpy() {
+        // the examples from numpy.polynomial.hermite_e
+        let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+        assert_close(&hermesub(&p, &Polynomial::new(vec![1.0, 2.0, 1.0])), &[0.0, 0.0, 2.0]);
+        assert_close(&hermemulx(&p), &[2.0, 7.0, 2.0, 3.0]);
+        let product = hermemul(&p, &Polynomial::new(vec![0.0, 1.0, 2.0]));
+        assert_close(&product, &[14.0, 15.0, 28.0, 7.0, 6.0]);
+        let (quotient, remainder) = hermediv(&product, &Polynomial::new(vec![0.0, 1.0, 2.0]));
+        assert_close(&quotient, &[1.0, 2.0, 3.0]);
+        assert_close(&remainder, &[]);
+        let (quotient, remainder) = hermediv(&Polynomial::new(vec![15.0, 17.0, 28.0, 7.0, 6.0]), &Polynomial::new(vec![0.0, 1.0, 2.0]));
+        assert_close(&quotient, &[1.0, 2.0, 3.0]);
+        assert_close(&remainder, &[1.0, 2.0]);
+        assert_eq!(hermeval(1.0, &p), 3.0);
+        assert_close(&hermeder(&Polynomial::new(vec![1.0, 1.0, 1.0, 1.0])), &[1.0, 2.0, 3.0]);
+        assert_close(&hermeint(&p, 0.0), &[1.0, 1.0, 1.0, 1.0]);
+        assert_close(&poly2herme(&Polynomial::new(vec![0.0, 1.0, 2.0, 3.0])), &[2.0, 10.0, 2.0, 3.0]);
+    }
+}
@@ -0,0 +1,82 @@
+use polynomial::Polynomial;
+use crate::polynomials::basis::{Coefficient, Recurrence, ratio, sub};
+
+// Following numpy.polynomial.hermite, the physicists' Hermite polynomials
+// H_n, with H_n(x) = 2^(n/2) He_n(sqrt(2) x). Addition is coefficientwise in
+// every basis, so `hermadd` serves here as well.
+
+/// x H_n = H_(n+1) / 2 + n H_(n-1)
+fn hermite<T: Coefficient>() -> Recurrence<T> {
+    Recurrence {
+        alpha: |_| ratio(1, 2),
+        beta: |_| T::zero(),
+        gamma: |n| ratio(n as i64, 1),
+    }
+}
+
+pub fn hermsub(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(sub(p.data(), q.data()))
+}
+
+pub fn hermmulx(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(hermite::<f32>().mulx(p.data()))
+}
+
+pub fn hermmul(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(hermite::<f32>().mul(p.data(), q.data()))
+}
+
+/// The quotient and remainder of p / q.
+pub fn hermdiv(p: &Polynomial<f32>, q: &Polynomial<f32>) -> (Polynomial<f32>, Polynomial<f32>) {
+    let (quotient, remainder) = hermite::<f32>().div(p.data(), q.data());
+    (Polynomial::new(quotient), Polynomial::new(remainder))
+}
+
+/// Evaluates the Hermite series p at x, by Clenshaw's algorithm.
+pub fn hermval(x: f32, p: &Polynomial<f32>) -> f32 {
+    hermite::<f32>().val(p.data(), x)
+}
+
+pub fn hermder(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(hermite::<f32>().der(p.data()))
+}
+
+/// The integral of p whose value at 0 is k.
+pub fn hermint(p: &Polynomial<f32>, k: f32) -> Polynomial<f32> {
+    Polynomial::new(hermite::<f32>().int(p.data(), k))
+}
+
+pub fn poly2herm(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(hermite::<f32>().power_to_basis(p.data()))
+}
+
+pub fn herm2poly(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(hermite::<f32>().to_power(p.data()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::basis::assert_close;
+
+    #[test]
+    fn test_hermite_numpy() {
+        // the examples from numpy.polynomial.hermite
+        let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+        assert_close(&hermsub(&p, &Polynomial::new(vec![1.0, 2.0, 1.0])), &[0.0, 0.0, 2.0]);
+        assert_close(&hermmulx(&p), &[2.0, 6.5, 1.0, 1.5]);
+        let product = hermmul(&p, &Polynomial::new(vec![0.0, 1.0, 2.0]));
+        assert_close(&product, &[52.0, 29.0, 52.0, 7.0, 6.0]);
+        let (quotient, remainder) = hermdiv(&product, &Polynomial::new(vec![0.0, 1.0, 2.0]));
+        assert_close(&quotient, &[1.0, 2.0, 3.0]);
+        assert_close(&remainder, &[]);
+        let (quotient, remainder) = hermdiv(&Polynomial::new(vec![54.0, 31.0, 52.0, 7.0, 6.0]), &Polynomial::new(vec![0.0, 1.0, 2.0]));
+        assert_close(&quotient, &[1.0, 2.0, 3.0]);
+        assert_close(&remainder, &[2.0, 2.0]);
+        assert_eq!(hermval(1.0, &p), 11.0);
+        assert_close(&hermder(&Polynomial::new(vec![1.0, 0.5, 0.5, 0.5])), &[1.0, 2.0, 3.0]);
+        assert_close(&hermint(&p, 0.0), &[1.0, 0.5, 0.5, 0.5]);
+        assert_close(&poly2herm(&Polynomial::new(vec![0.0, 1.0, 2.0, 3.0])), &[1.0, 2.75, 0.5, 0.375]);
+        assert_close(&herm2poly(&Polynomial::new(vec![1.0, 2.75, 0.5, 0.375])), &[0.0, 1.0, 2.0, 3.0]);
+    }
+}
@@ -0,0 +1,87 @@
+use polynomial::Polynomial;
+use crate::polynomials::basis::{Coefficient, Recurrence, ratio, add, sub};
+
+// Following numpy.polynomial.laguerre, the Laguerre polynomials L_n, with
+// L_n(0) = 1.
+
+/// x L_n = -(n + 1) L_(n+1) + (2n + 1) L_n - n L_(n-1)
+fn laguerre<T: Coefficient>() -> Recurrence<T> {
+    Recurrence {
+        alpha: |n| ratio(-(n as i64) - 1, 1),
+        beta: |n| ratio(2 * n as i64 + 1, 1),
+        gamma: |n| ratio(-(n as i64), 1),
+    }
+}
+
+pub fn lagadd(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(add(p.data(), q.data()))
+}
+
+pub fn lagsub(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(sub(p.data(), q.data()))
+}
+
+pub fn lagmulx(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(laguerre::<f32>().mulx(p.data()))
+}
+
+pub fn lagmul(p: &Polynomial<f32>, q: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(laguerre::<f32>().mul(p.data(), q.data()))
+}
+
+/// The quotient and remainder of p / q.
+pub fn lagdiv(p: &Polynomial<f32>, q: &Polynomial<f32>) -> (Polynomial<f32>, Polynomial<f32>) {
+    let (quotient, remainder) = laguerre::<f32>().div(p.data(), q.data());
+    (Polynomial::new(quotient), Polynomial::new(remainder))
+}
+
+/// Evaluates the Laguerre series p at x, by Clenshaw's algorithm.
+pub fn lagval(x: f32, p: &Polynomial<f32>) -> f32 {
+    laguerre::<f32>().val(p.data(), x)
+}
+
+pub fn lagder(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(laguerre::<f32>().der(p.data()))
+}
+
+/// The integral of p whose value at 0 is k.
+pub fn lagint(p: &Polynomial<f32>, k: f32) -> Polynomial<f32> {
+    Polynomial::new(laguerre::<f32>().int(p.data(), k))
+}
+
+pub fn poly2lag(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(laguerre::<f32>().power_to_basis(p.data()))
+}
+
+pub fn lag2poly(p: &Polynomial<f32>) -> Polynomial<f32> {
+    Polynomial::new(laguerre::<f32>().to_power(p.data()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polynomials::basis::assert_close;
+
+    #[test]
+    fn test_laguerre_numpy() {
+        // the examples from numpy.polynomial.laguerre
+        let p = Polynomial::new(vec![1.0, 2.0, 3.0]);
+        assert_close(&lagadd(&p, &Polynomial::new(vec![1.0, 2.0, 3.0, 4.0])), &[2.0, 4.0, 6.0, 4.0]);
+        assert_close(&lagsub(&Polynomial::new(vec![1.0, 2.0, 3.0, 4.0]), &p), &[0.0, 0.0, 0.0, 4.0]);
+        assert_close(&lagmulx(&p), &[-1.0, -1.0, 11.0, -9.0]);
+        let product = lagmul(&p, &Polynomial::new(vec![0.0, 1.0, 2.0]));
+        assert_close(&product, &[8.0, -13.0, 38.0, -51.0, 36.0]);
+        let (quotient, remainder) = lagdiv(&product, &Polynomial::new(vec![0.0, 1.0, 2.0]));
+        assert_close(&quotient, &[1.0, 2.0, 3.0]);
+        assert_close(&remainder, &[]);
+        let (quotient, remainder) = lagdiv(&Polynomial::new(vec![9.0, -12.0, 38.0, -51.0, 36.0]), &Polynomial::new(vec![0.0, 1.0, 2.0]));
+        assert_close(&quotient, &[1.0, 2.0, 3.0]);
+        assert_close(&remainder, &[1.0, 1.0]);
+        // L_1(2) = -1 and L_2(2) = -1
+        assert_eq!(lagval(2.0, &p), -4.0);
+        assert_close(&lagder(&Polynomial::new(vec![1.0, 1.0, 1.0, -3.0])), &[1.0, 2.0, 3.0]);
+        assert_close(&lagint(&p, 0.0), &[1.0, 1.0, 1.0, -3.0]);
+        assert_close(&poly2lag(&Polynomial::new(vec![0.0, 1.0, 2.0, 3.0])), &[23.0, -63.0, 58.0, -18.0]);
+        assert_close(&lag2poly(&Polynomial::new(vec![23.0, -63.0, 58.0, -18.0])), &[0.0, 1.0, 2.0, 3.0]);
+    }
+}
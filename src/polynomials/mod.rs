@@ -1,7 +1,27 @@
+pub(crate) mod basis;
 mod hermite;
+mod hermite_physicists;
+mod chebyshev;
+mod laguerre;
+mod graph_families;
+mod expansions;
+mod complement;
 mod process;
 
 pub use hermite::{hermemulx, hermadd, poly2herme};
 //, herme2poly};
-pub use hermite::herme2poly;
+pub use hermite::{herme2poly, herme_table};
+pub(crate) use hermite::complete_matching_counts;
+pub use hermite::{hermesub, hermemul, hermediv, hermeval, hermeder, hermeint};
+pub use hermite_physicists::{hermsub, hermmulx, hermmul, hermdiv, hermval, hermder, hermint, poly2herm, herm2poly};
+pub use chebyshev::{chebadd, chebsub, chebmulx, chebmul, chebdiv, chebval, chebder, chebint, poly2cheb, cheb2poly};
+pub use chebyshev::{chebuadd, chebusub, chebumulx, chebumul, chebudiv, chebuval, chebuder, chebuint, poly2chebu, chebu2poly};
+pub use laguerre::{lagadd, lagsub, lagmulx, lagmul, lagdiv, lagval, lagder, lagint, poly2lag, lag2poly};
+pub use graph_families::{complete_graph_matching_polynomial, path_matching_polynomial, cycle_matching_polynomial,
+    complete_bipartite_matching_polynomial};
+pub use expansions::{signed_matching_polynomial, poly2herme_exact, herme2poly_exact, poly2cheb_exact, cheb2poly_exact,
+    poly2falling, falling2poly};
+pub use expansions::{matching_polynomial_to_herme, matching_polynomial_from_herme, matching_polynomial_to_cheb,
+    matching_polynomial_from_cheb, matching_polynomial_to_falling_factorial, matching_polynomial_from_falling_factorial};
 pub use process::sign_flip;
+pub use complement::{complement_polynomial, complement_polynomial_inverse};
@@ -0,0 +1,130 @@
+use crate::reconstruction::{ReconstructedPolynomial, DeckError};
+
+/// Sums the edge deck coefficientwise. Each k-matching of G survives the
+/// deletion of any of the (|E| - k) edges it does not use, so
+///     sum_e p_k(G - e) = (|E| - k) p_k(G)
+/// where p_k is the number of k-matchings, i.e. the coefficient of x^(n - 2k).
+pub fn sum_edge_deck<T: AsRef<[u64]>>(edge_deck: &[T], graph_size: usize) -> Vec<u64> {
+    let mut sum = vec![0; graph_size + 1];
+    for card in edge_deck {
+        sum.iter_mut()
+            .zip(card.as_ref().iter())
+            .for_each(|(x, y)| *x += y);
+    }
+    sum
+}
+
+/// Checks the edge deck identity above against the polynomial of the graph.
+pub fn check_edge_deck_identity<T: AsRef<[u64]>>(coefficients: &[u64], edge_deck: &[T], graph_size: usize) -> bool {
+    let edge_count = edge_deck.len() as u64;
+    let sum = sum_edge_deck(edge_deck, graph_size);
+    (0..=graph_size / 2).all(|k| {
+        let index = graph_size - 2 * k;
+        let coefficient = coefficients.get(index).copied().unwrap_or(0);
+        sum[index] == edge_count.saturating_sub(k as u64) * coefficient
+    })
+}
+
+/// Reconstructs m(G) from the matching polynomials of its edge-deleted
+/// subgraphs G - e, as returned by `calculate_edge_deck_polynomials` or by
+/// running an engine on each graph from `get_edge_deck`.
+///
+/// Dividing the identity in `sum_edge_deck` through by (|E| - k) gives every
+/// p_k with k < |E|. The remaining one, p_|E|, is 1 if and only if the edges
+/// of G are pairwise disjoint. With three or more edges this happens exactly
+/// when every card is itself a set of disjoint edges, but with two edges the
+/// path on three nodes and two disjoint edges have the same edge deck, so that
+/// coefficient is flagged as undetermined. A deck which cannot have come from
+/// a graph is reported as an error.
+pub fn reconstruct_from_edge_deck<T: AsRef<[u64]>>(edge_deck: &[T], graph_size: usize) -> Result<ReconstructedPolynomial, DeckError> {
+    let edge_count = edge_deck.len();
+    let sum = sum_edge_deck(edge_deck, graph_size);
+    let mut coefficients = vec![0; graph_size + 1];
+    let mut undetermined_coefficient = None;
+
+    for k in 0..=graph_size / 2 {
+        let index = graph_size - 2 * k;
+        if k < edge_count {
+            let divisor = (edge_count - k) as u64;
+            if !sum[index].is_multiple_of(divisor) {
+                return Err(DeckError::NotDivisible { coefficient: index })
+            }
+            coefficients[index] = sum[index] / divisor;
+        } else if k == edge_count {
+            coefficients[index] = match edge_count {
+                0 | 1 => 1,
+                2 => {
+                    undetermined_coefficient = Some(index);
+                    0
+                },
+                _ => {
+                    // every card has to consist of |E| - 1 disjoint edges
+                    let card_index = index + 2;
+                    let all_matchings = edge_deck
+                        .iter()
+                        .all(|card| card.as_ref().get(card_index).copied().unwrap_or(0) == 1);
+                    all_matchings as u64
+                },
+            };
+        }
+    }
+    Ok(ReconstructedPolynomial {
+        coefficients,
+        undetermined_coefficient,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BinaryGraph;
+    use crate::binary_graph_matching::calculate_edge_deck_polynomials;
+
+    #[test]
+    fn test_reconstruct_from_edge_deck() {
+        // the 5-cycle with a pendant node
+        let data = [
+            0b110010, 0b11000, 0b1100, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let graph = BinaryGraph::from(data);
+        let (matching_poly, edge_deck_polies) = calculate_edge_deck_polynomials(graph);
+        let edge_deck = edge_deck_polies
+            .into_iter()
+            .map(|(_, poly)| poly)
+            .collect::<Vec<_>>();
+
+        assert!(check_edge_deck_identity(&matching_poly, &edge_deck, 6));
+        let reconstruction = reconstruct_from_edge_deck(&edge_deck, 6).unwrap();
+        assert_eq!(reconstruction.undetermined_coefficient, None);
+        assert_eq!(reconstruction.coefficients[..], matching_poly[..=6]);
+    }
+
+    #[test]
+    fn test_reconstruct_from_edge_deck_matching() {
+        // three disjoint edges
+        let data = [
+            0b110000, 0b10000, 0b1100, 0b100, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let graph = BinaryGraph::from(data);
+        let (matching_poly, edge_deck_polies) = calculate_edge_deck_polynomials(graph);
+        let edge_deck = edge_deck_polies
+            .into_iter()
+            .map(|(_, poly)| poly)
+            .collect::<Vec<_>>();
+        let reconstruction = reconstruct_from_edge_deck(&edge_deck, 6).unwrap();
+        assert_eq!(reconstruction.coefficients, vec![1, 0, 3, 0, 3, 0, 1]);
+        assert_eq!(reconstruction.coefficients[..], matching_poly[..=6]);
+    }
+
+    #[test]
+    fn test_reconstruct_from_invalid_edge_deck() {
+        // two cards on three nodes, whose x^3 terms sum to 3, which |E| = 2
+        // does not divide
+        let edge_deck = [[0, 1, 0, 1], [0, 2, 0, 2]];
+        assert_eq!(reconstruct_from_edge_deck(&edge_deck, 3), Err(DeckError::NotDivisible { coefficient: 3 }));
+    }
+}
@@ -0,0 +1,5 @@
+mod vertex_deck;
+mod edge_deck;
+
+pub use self::vertex_deck::{ReconstructedPolynomial, DeckError, reconstruct_from_deck, reconstruct_from_deck_with_graph, count_perfect_matchings};
+pub use self::edge_deck::{sum_edge_deck, check_edge_deck_identity, reconstruct_from_edge_deck};
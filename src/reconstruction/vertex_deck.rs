@@ -0,0 +1,197 @@
+use std::fmt;
+use crate::binary_graph_matching::BinaryGraph;
+
+/// The result of rebuilding a matching polynomial from a deck. The
+/// coefficients are signless and indexed by the power of x, as with the
+/// output of the engines. If the deck does not pin down one of the
+/// coefficients, that coefficient is left at zero and its index is recorded
+/// in `undetermined_coefficient`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReconstructedPolynomial {
+    pub coefficients: Vec<u64>,
+    pub undetermined_coefficient: Option<usize>,
+}
+
+/// Why a vertex or edge deck could not have come from a graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeckError {
+    /// the coefficient of x^coefficient in the sum of the deck is not
+    /// divisible by the count the deck identity divides it by: coefficient + 1
+    /// for a vertex deck, whose sum is the derivative of m(G), and |E| - k for
+    /// the coefficient of x^(n - 2k) in an edge deck
+    NotDivisible { coefficient: usize },
+    /// the reconstructed polynomial would not be monic of degree n
+    NotMonic,
+}
+
+impl fmt::Display for DeckError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeckError::NotDivisible { coefficient } =>
+                write!(f, "coefficient {} of the sum of the deck does not divide exactly", coefficient),
+            DeckError::NotMonic =>
+                write!(f, "the cards do not each have leading term x^(n-1)"),
+        }
+    }
+}
+
+impl std::error::Error for DeckError {}
+
+/// Reconstructs m(G) from the matching polynomials of its vertex-deleted
+/// subgraphs G - v. The deck may be given as the output of
+/// `get_matching_polies_stable_graph` (without its first entry), or as the
+/// engine output for each graph returned by `get_deck`.
+///
+/// Since m'(G, x) = sum_v m(G - v, x), integrating the sum of the deck gives
+/// every coefficient of m(G) apart from the constant term. The constant term
+/// counts the perfect matchings. When the graph has an odd number of nodes it
+/// is zero; otherwise the deck polynomials alone do not determine it, and it
+/// is flagged as undetermined. A deck which cannot have come from a graph is
+/// reported as an error.
+pub fn reconstruct_from_deck<T: AsRef<[u64]>>(deck: &[T]) -> Result<ReconstructedPolynomial, DeckError> {
+    let graph_size = deck.len();
+    if graph_size == 0 {
+        // the empty graph has just the empty matching
+        return Ok(ReconstructedPolynomial {
+            coefficients: vec![1],
+            undetermined_coefficient: None,
+        })
+    }
+
+    let mut coefficients = vec![0; graph_size + 1];
+    for (i, coefficient) in coefficients.iter_mut().enumerate().skip(1) {
+        // the coefficient of x^(i-1) in the derivative
+        let derivative_coefficient = deck
+            .iter()
+            .map(|card| card.as_ref().get(i - 1).copied().unwrap_or(0))
+            .sum::<u64>();
+        if !derivative_coefficient.is_multiple_of(i as u64) {
+            return Err(DeckError::NotDivisible { coefficient: i - 1 })
+        }
+        *coefficient = derivative_coefficient / i as u64;
+    }
+
+    if coefficients[graph_size] != 1 {
+        return Err(DeckError::NotMonic)
+    }
+
+    // an odd graph has no perfect matchings
+    let undetermined_coefficient = if graph_size.is_multiple_of(2) {
+        Some(0)
+    } else {
+        None
+    };
+    Ok(ReconstructedPolynomial {
+        coefficients,
+        undetermined_coefficient,
+    })
+}
+
+/// As `reconstruct_from_deck`, but with the original graph available to
+/// settle the constant term by counting its perfect matchings directly.
+pub fn reconstruct_from_deck_with_graph<T: AsRef<[u64]>>(deck: &[T], graph: BinaryGraph) -> Result<Vec<u64>, DeckError> {
+    let mut reconstruction = reconstruct_from_deck(deck)?;
+    if let Some(index) = reconstruction.undetermined_coefficient {
+        reconstruction.coefficients[index] = count_perfect_matchings(graph);
+    }
+    Ok(reconstruction.coefficients)
+}
+
+/// Counts the perfect matchings of the graph, i.e. the constant term of its
+/// matching polynomial, without running the full recursion: the first
+/// remaining node has to be matched to one of its neighbours, so we branch
+/// only over those.
+pub fn count_perfect_matchings(graph: BinaryGraph) -> u64 {
+    let neighbourhoods = graph.neighbourhoods();
+    _count_perfect_matchings(&neighbourhoods, graph.node_mask())
+}
+
+fn _count_perfect_matchings(neighbourhoods: &[usize], remaining_nodes: usize) -> u64 {
+    if remaining_nodes == 0 {
+        return 1
+    }
+    if remaining_nodes.count_ones() % 2 == 1 {
+        return 0
+    }
+    let node = remaining_nodes.trailing_zeros() as usize;
+    let mut partners = neighbourhoods[node] & remaining_nodes;
+    let mut count = 0;
+    while partners != 0 {
+        let partner = partners.trailing_zeros() as usize;
+        partners &= partners - 1;
+        count += _count_perfect_matchings(neighbourhoods, remaining_nodes & !(1 << node) & !(1 << partner));
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::{Graph, get_deck};
+    use crate::calculate_matching_polynomial_pointer;
+
+    #[test]
+    fn test_reconstruct_from_deck_even() {
+        // the 5-cycle with a pendant node, so that there are perfect matchings
+        let data = [
+            0b110010, 0b11000, 0b1100, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let graph = BinaryGraph::from(data);
+        let graph_size = graph.graph_size();
+        let matching_polynomial = calculate_matching_polynomial_pointer(graph);
+        let deck = get_deck(graph)
+            .into_iter()
+            .map(calculate_matching_polynomial_pointer)
+            .collect::<Vec<_>>();
+
+        let reconstruction = reconstruct_from_deck(&deck).unwrap();
+        assert_eq!(reconstruction.undetermined_coefficient, Some(0));
+        assert_eq!(reconstruction.coefficients[1..], matching_polynomial[1..=graph_size]);
+        assert_eq!(reconstruction.coefficients[0], 0);
+
+        let coefficients = reconstruct_from_deck_with_graph(&deck, graph).unwrap();
+        assert_eq!(coefficients[..], matching_polynomial[..=graph_size]);
+    }
+
+    #[test]
+    fn test_reconstruct_from_deck_odd() {
+        let fc_data = [
+            0b11111, 0b1111, 0b111, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0
+        ];
+        let graph = BinaryGraph::from(fc_data);
+        let deck = get_deck(graph)
+            .into_iter()
+            .map(|card| calculate_matching_polynomial_pointer(card).to_vec())
+            .collect::<Vec<_>>();
+
+        let reconstruction = reconstruct_from_deck(&deck).unwrap();
+        assert_eq!(reconstruction.undetermined_coefficient, None);
+        assert_eq!(reconstruction.coefficients, vec![0, 15, 0, 10, 0, 1]);
+    }
+
+    #[test]
+    fn test_reconstruct_from_invalid_deck() {
+        // the x terms sum to 1, which is not the derivative of an integer x^2 term
+        let deck = [vec![1, 1, 1], vec![1, 0, 1], vec![1, 0, 1]];
+        assert_eq!(reconstruct_from_deck(&deck), Err(DeckError::NotDivisible { coefficient: 1 }));
+        // two cards of 2x would integrate to 2x^2
+        assert_eq!(reconstruct_from_deck(&[vec![0, 2], vec![0, 2]]), Err(DeckError::NotMonic));
+        // the deck of the triangle
+        assert_eq!(reconstruct_from_deck(&[vec![1, 0, 1], vec![1, 0, 1], vec![1, 0, 1]]).unwrap().coefficients, vec![0, 3, 0, 1]);
+    }
+
+    #[test]
+    fn test_count_perfect_matchings() {
+        let fc_data = [
+            0b111111, 0b11111, 0b1111, 0b111, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        assert_eq!(count_perfect_matchings(BinaryGraph::from(fc_data)), 15);
+    }
+}
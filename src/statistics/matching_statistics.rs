@@ -0,0 +1,220 @@
+use num::{BigUint, ToPrimitive, Zero};
+
+/// The monomer-dimer statistics of a graph, read off its matching polynomial.
+/// Weighting each matching with k edges by lambda^k gives the partition
+/// function
+///     Z(lambda) = sum_k p(G, k) lambda^k
+/// and a distribution on the matchings of the graph, which at lambda = 1 is
+/// the uniform one. The statistics below are those of the number of edges in
+/// a matching drawn from it.
+///
+/// By Heilmann and Lieb the roots of the matching polynomial are real, and so
+/// the p(G, k) are log-concave, and hence unimodal, in k.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchingStatistics {
+    /// entry k is p(G, k), the number of matchings with k edges, up to the
+    /// size of a maximum matching
+    pub counts: Vec<BigUint>,
+    node_count: usize,
+}
+
+impl MatchingStatistics {
+    /// From the signless coefficients that the engines return, indexed by the
+    /// power of x, for a graph on n nodes.
+    pub fn from_coefficients(coefficients: &[u64], n: usize) -> MatchingStatistics {
+        let coefficients = coefficients
+            .iter()
+            .map(|x| BigUint::from(*x))
+            .collect::<Vec<_>>();
+        MatchingStatistics::from_big_coefficients(&coefficients, n)
+    }
+
+    /// As `from_coefficients`, for the exact engines.
+    pub fn from_big_coefficients(coefficients: &[BigUint], n: usize) -> MatchingStatistics {
+        assert!(coefficients.len() > n, "a graph on {} nodes has {} coefficients", n, n + 1);
+        let mut counts = (0..=n / 2)
+            .map(|k| coefficients[n - 2 * k].clone())
+            .collect::<Vec<_>>();
+        while counts.len() > 1 && counts.last().is_some_and(|x| x.is_zero()) {
+            counts.pop();
+        }
+        MatchingStatistics {
+            counts,
+            node_count: n,
+        }
+    }
+
+    /// The size of a maximum matching.
+    pub fn matching_number(&self) -> usize {
+        self.counts.len() - 1
+    }
+
+    /// The probability of each matching size under the lambda-weighted
+    /// distribution, worked out in logarithms so that neither the counts nor
+    /// the powers of lambda overflow.
+    pub fn distribution(&self, lambda: f64) -> Vec<f64> {
+        let log_weights = self.log_weights(lambda);
+        let max = log_weights.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let weights = log_weights.iter().map(|x| (x - max).exp()).collect::<Vec<_>>();
+        let total = weights.iter().sum::<f64>();
+        weights.iter().map(|x| x / total).collect()
+    }
+
+    /// The mean number of edges in a matching; lambda = 1 gives the mean
+    /// under the uniform distribution.
+    pub fn mean(&self, lambda: f64) -> f64 {
+        self.distribution(lambda)
+            .iter()
+            .enumerate()
+            .map(|(k, q)| k as f64 * q)
+            .sum()
+    }
+
+    /// The variance of the number of edges in a matching.
+    pub fn variance(&self, lambda: f64) -> f64 {
+        let distribution = self.distribution(lambda);
+        let mean = distribution.iter().enumerate().map(|(k, q)| k as f64 * q).sum::<f64>();
+        distribution
+            .iter()
+            .enumerate()
+            .map(|(k, q)| (k as f64 - mean).powi(2) * q)
+            .sum()
+    }
+
+    /// ln Z(lambda).
+    pub fn log_partition(&self, lambda: f64) -> f64 {
+        let log_weights = self.log_weights(lambda);
+        let max = log_weights.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        max + log_weights.iter().map(|x| (x - max).exp()).sum::<f64>().ln()
+    }
+
+    /// The first and second derivatives of ln Z in lambda, which are
+    ///     mean / lambda    and    (variance - mean) / lambda^2
+    pub fn log_partition_derivatives(&self, lambda: f64) -> (f64, f64) {
+        let mean = self.mean(lambda);
+        let variance = self.variance(lambda);
+        (mean / lambda, (variance - mean) / (lambda * lambda))
+    }
+
+    /// The expected fraction of nodes covered by the matching, 2 mean / n.
+    pub fn dimer_density(&self, lambda: f64) -> f64 {
+        match self.node_count {
+            0 => 0.0,
+            n => 2.0 * self.mean(lambda) / n as f64,
+        }
+    }
+
+    /// The entropy, in nats, of the lambda-weighted distribution on the
+    /// matchings themselves, ln Z - mean ln lambda. At lambda = 1 it is the
+    /// logarithm of the number of matchings.
+    pub fn entropy(&self, lambda: f64) -> f64 {
+        self.log_partition(lambda) - self.mean(lambda) * lambda.ln()
+    }
+
+    /// Whether p(G, k)^2 >= p(G, k - 1) p(G, k + 1) for every k, checked
+    /// exactly.
+    pub fn is_log_concave(&self) -> bool {
+        self.counts
+            .windows(3)
+            .all(|window| &window[1] * &window[1] >= &window[0] * &window[2])
+    }
+
+    /// Whether the counts rise to a peak and then fall.
+    pub fn is_unimodal(&self) -> bool {
+        let mut falling = false;
+        for window in self.counts.windows(2) {
+            if window[1] < window[0] {
+                falling = true;
+            } else if falling && window[1] > window[0] {
+                return false
+            }
+        }
+        true
+    }
+
+    /// ln p(G, k) + k ln lambda for each k
+    fn log_weights(&self, lambda: f64) -> Vec<f64> {
+        assert!(lambda > 0.0, "the weight lambda should be positive");
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(k, count)| log(count) + k as f64 * lambda.ln())
+            .collect()
+    }
+}
+
+/// the natural logarithm of a count, which may be beyond the range of f64
+fn log(count: &BigUint) -> f64 {
+    let bits = count.bits();
+    if bits <= 1000 {
+        return count.to_f64().unwrap().ln()
+    }
+    // keep the leading 64 bits
+    let shift = bits - 64;
+    (count >> shift).to_f64().unwrap().ln() + shift as f64 * std::f64::consts::LN_2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_graph_matching::calculate_matching_polynomial_pointer;
+    use crate::graph_operations::graph_from_edges;
+
+    fn close(x: f64, y: f64) -> bool {
+        (x - y).abs() <= 1e-9 * y.abs().max(1.0)
+    }
+
+    #[test]
+    fn test_matching_statistics() {
+        // the path on 4 nodes has 1, 3 and 1 matchings with 0, 1 and 2 edges
+        let path = calculate_matching_polynomial_pointer(graph_from_edges(4, &[(0, 1), (1, 2), (2, 3)]));
+        let statistics = MatchingStatistics::from_coefficients(&path, 4);
+        assert_eq!(statistics.counts, vec![BigUint::from(1u8), BigUint::from(3u8), BigUint::from(1u8)]);
+        assert_eq!(statistics.matching_number(), 2);
+        assert!(close(statistics.mean(1.0), 1.0));
+        assert!(close(statistics.variance(1.0), 0.4));
+        assert!(close(statistics.log_partition(1.0), 5f64.ln()));
+        assert!(close(statistics.entropy(1.0), 5f64.ln()));
+        assert!(close(statistics.dimer_density(1.0), 0.5));
+
+        // Z(2) = 1 + 6 + 4, with mean 14 / 11 and second moment 22 / 11
+        let lambda = 2.0;
+        let mean = 14.0 / 11.0;
+        let variance = 2.0 - mean * mean;
+        assert!(close(statistics.mean(lambda), mean));
+        assert!(close(statistics.variance(lambda), variance));
+        assert!(close(statistics.log_partition(lambda), 11f64.ln()));
+        // Z'(2) / Z(2) = 7 / 11, and (ln Z)'' = Z'' / Z - (Z' / Z)^2 = 2 / 11 - 49 / 121
+        let (first, second) = statistics.log_partition_derivatives(lambda);
+        assert!(close(first, 7.0 / 11.0));
+        assert!(close(second, 2.0 / 11.0 - 49.0 / 121.0));
+        assert!(close(statistics.entropy(lambda), 11f64.ln() - mean * 2f64.ln()));
+
+        // no overflow for large lambda, where the maximum matching dominates
+        assert!(close(statistics.mean(1e300), 2.0));
+
+        // Heilmann-Lieb, on the engines' output
+        let complete_edges = (0..12).flat_map(|u| (u + 1..12).map(move |v| (u, v))).collect::<Vec<_>>();
+        let graphs = [
+            (graph_from_edges(12, &complete_edges), 12),
+            (graph_from_edges(7, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 6)]), 7),
+            (graph_from_edges(5, &[(0, 1), (0, 2), (0, 3), (0, 4)]), 5),
+        ];
+        for (graph, n) in graphs {
+            let statistics = MatchingStatistics::from_coefficients(&calculate_matching_polynomial_pointer(graph), n);
+            assert!(statistics.is_log_concave());
+            assert!(statistics.is_unimodal());
+        }
+        // the star has matching number 1 however many nodes it has
+        let star = calculate_matching_polynomial_pointer(graph_from_edges(5, &[(0, 1), (0, 2), (0, 3), (0, 4)]));
+        assert_eq!(MatchingStatistics::from_coefficients(&star, 5).matching_number(), 1);
+
+        // sequences which are neither
+        let statistics = MatchingStatistics {
+            counts: [1u8, 5, 2, 3].iter().map(|x| BigUint::from(*x)).collect(),
+            node_count: 6,
+        };
+        assert!(!statistics.is_log_concave());
+        assert!(!statistics.is_unimodal());
+    }
+}
@@ -0,0 +1,3 @@
+mod matching_statistics;
+
+pub use self::matching_statistics::MatchingStatistics;
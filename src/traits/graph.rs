@@ -6,6 +6,7 @@ pub trait Graph: Clone + Debug {
     fn edgeless_node_count(&self) -> usize;
     fn graph_size(&self) -> usize;
     fn edge_count(&self) -> usize;
+    fn edges(&self) -> Vec<(usize, usize)>;
     fn edgeless(&self) -> bool;
     fn initial_graph_size(&self) -> usize;
     fn get_graph_primes(self) -> (Self, Self);
@@ -25,3 +26,14 @@ pub fn get_deck<T: Graph>(graph: T) -> Vec<T>{
     }
     deck
 }
+
+pub fn get_edge_deck<T: Graph>(graph: T) -> Vec<T>{
+    let mut deck = Vec::<T>::new();
+    let graph_size = graph.initial_graph_size();
+    for (node1, node2) in graph.edges() {
+        let mut current_graph = graph.clone();
+        current_graph.remove_edge(node1, node2, graph_size);
+        deck.push(current_graph);
+    }
+    deck
+}
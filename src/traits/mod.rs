@@ -1,2 +1,2 @@
 mod graph;
-pub use graph::{Graph, get_deck};
+pub use graph::{Graph, get_deck, get_edge_deck};
@@ -0,0 +1,3 @@
+mod strip;
+
+pub use self::strip::{Boundary, strip_matching_polynomial, strip_graph};
@@ -0,0 +1,194 @@
+use crate::binary_graph_matching::BinaryGraph;
+use num::{BigUint, Zero, One};
+use std::collections::HashMap;
+use std::mem::size_of;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+
+/// The boundary conditions of a width x length strip of the square lattice.
+/// Free is the grid P_width x P_length, Cylindrical joins the top and bottom
+/// of each column (C_width x P_length), and Toroidal also joins the last
+/// column back to the first (C_width x C_length). A wrap that would double an
+/// existing edge, i.e. around a cycle of length less than 3, is left out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    Free,
+    Cylindrical,
+    Toroidal,
+}
+
+/// Calculates the matching polynomial of a width x length strip with the
+/// transfer-matrix method, with exact coefficients indexed by the power of x
+/// as in the other engines.
+///
+/// We sweep along the strip a column at a time. The state between two
+/// columns is the set of rows whose horizontal edge to the next column is in
+/// the matching, so there are 2^width states, each carrying the generating
+/// polynomial (by number of edges) of the partial matchings that lead to it.
+/// Moving to the next column, each row not already covered can be left
+/// empty, matched vertically to the row below it, or matched forwards. For
+/// the torus we fix the set of rows using the wrap-around edges, start from
+/// it and insist on finishing in it, and sum over the choices of that set.
+pub fn strip_matching_polynomial(width: usize, length: usize, boundary: Boundary) -> Vec<BigUint> {
+    let graph_size = width * length;
+    if graph_size == 0 {
+        return vec![BigUint::one()]
+    }
+    assert!(width < MAX_NODES, "the states are kept in a mask of {} bits", MAX_NODES);
+
+    let wrap_columns = boundary != Boundary::Free && width >= 3;
+    let wrap_length = boundary == Boundary::Toroidal && length >= 3;
+    let transitions = (0..1usize << width)
+        .map(|covered| column_transitions(covered, width, wrap_columns))
+        .collect::<Vec<_>>();
+
+    // the number of k-matchings, indexed by k
+    let mut edge_counts = vec![BigUint::zero(); graph_size / 2 + 1];
+    let start_states = if wrap_length { 0..1usize << width } else { 0..1 };
+    for start_state in start_states {
+        let mut states = HashMap::new();
+        // the wrap-around edges are counted as they leave the last column
+        states.insert(start_state, vec![BigUint::one()]);
+        for column in 0..length {
+            let last_column = column == length - 1;
+            let mut new_states: HashMap<usize, Vec<BigUint>> = HashMap::new();
+            for (covered, poly) in states {
+                for ((forward, edges), multiplicity) in transitions[covered].iter() {
+                    let allowed_forward = match last_column {
+                        true if wrap_length => start_state,
+                        true => 0,
+                        false => *forward,
+                    };
+                    if *forward != allowed_forward {
+                        continue
+                    }
+                    let new_poly = new_states.entry(*forward).or_default();
+                    if new_poly.len() < poly.len() + edges {
+                        new_poly.resize(poly.len() + edges, BigUint::zero());
+                    }
+                    for (k, coefficient) in poly.iter().enumerate() {
+                        new_poly[k + edges] += coefficient * *multiplicity;
+                    }
+                }
+            }
+            states = new_states;
+        }
+        if let Some(poly) = states.get(&start_state) {
+            for (k, coefficient) in poly.iter().enumerate() {
+                edge_counts[k] += coefficient;
+            }
+        }
+    }
+
+    let mut matching_poly = vec![BigUint::zero(); graph_size + 1];
+    for (k, count) in edge_counts.into_iter().enumerate() {
+        matching_poly[graph_size - 2 * k] = count;
+    }
+    matching_poly
+}
+
+/// The ways of filling one column given the rows already covered from the
+/// previous column, as (forward rows, edges placed) -> multiplicity.
+fn column_transitions(covered: usize, width: usize, wrap_columns: bool) -> HashMap<(usize, usize), u64> {
+    fn fill(row: usize, covered: usize, forward: usize, edges: usize, width: usize,
+            transitions: &mut HashMap<(usize, usize), u64>) {
+        if row == width {
+            *transitions.entry((forward, edges)).or_insert(0) += 1;
+            return
+        }
+        if covered & (1 << row) != 0 {
+            return fill(row + 1, covered, forward, edges, width, transitions)
+        }
+        // leave the row empty
+        fill(row + 1, covered, forward, edges, width, transitions);
+        // match it forwards to the next column
+        fill(row + 1, covered, forward | (1 << row), edges + 1, width, transitions);
+        // match it vertically to the row below
+        if row + 1 < width && covered & (1 << (row + 1)) == 0 {
+            fill(row + 2, covered | (1 << (row + 1)), forward, edges + 1, width, transitions);
+        }
+    }
+
+    let mut transitions = HashMap::new();
+    fill(0, covered, 0, 0, width, &mut transitions);
+    let wrap_rows = 1 | (1 << (width - 1));
+    if wrap_columns && covered & wrap_rows == 0 {
+        // use the edge from the bottom row back round to the top
+        let mut wrapped = HashMap::new();
+        fill(0, covered | wrap_rows, 0, 1, width, &mut wrapped);
+        for (key, multiplicity) in wrapped {
+            *transitions.entry(key).or_insert(0) += multiplicity;
+        }
+    }
+    transitions
+}
+
+/// Builds the width x length strip as a BinaryGraph, with node c * width + r
+/// in row r of column c, for checking the transfer matrices against the other
+/// engines on small instances.
+pub fn strip_graph(width: usize, length: usize, boundary: Boundary) -> BinaryGraph {
+    let graph_size = width * length;
+    assert!(graph_size <= MAX_NODES, "the graph holds at most {} nodes", MAX_NODES);
+    let mut edges = Vec::new();
+    for column in 0..length {
+        for row in 0..width {
+            let node = column * width + row;
+            if row + 1 < width {
+                edges.push((node, node + 1));
+            }
+            if column + 1 < length {
+                edges.push((node, node + width));
+            }
+        }
+        if boundary != Boundary::Free && width >= 3 {
+            edges.push((column * width, column * width + width - 1));
+        }
+    }
+    if boundary == Boundary::Toroidal && length >= 3 {
+        for row in 0..width {
+            edges.push((row, (length - 1) * width + row));
+        }
+    }
+
+    let mut data = [0; MAX_NODES];
+    for (node, row) in data.iter_mut().enumerate().take(graph_size) {
+        *row = 1 << (graph_size - node - 1);
+    }
+    for (u, v) in edges {
+        data[u] |= 1 << (graph_size - v - 1);
+    }
+    BinaryGraph::from(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_matching_polynomial_pointer;
+
+    #[test]
+    fn test_strip_against_pointer() {
+        for boundary in [Boundary::Free, Boundary::Cylindrical, Boundary::Toroidal] {
+            for (width, length) in [(1, 5), (2, 4), (3, 3), (3, 4), (4, 4), (4, 3)] {
+                let graph_size = width * length;
+                let matching_poly = strip_matching_polynomial(width, length, boundary);
+                let pointer_poly = calculate_matching_polynomial_pointer(strip_graph(width, length, boundary));
+                let expected = pointer_poly[..=graph_size]
+                    .iter()
+                    .map(|x| BigUint::from(*x))
+                    .collect::<Vec<_>>();
+                assert_eq!(matching_poly, expected, "{:?} {} x {}", boundary, width, length);
+            }
+        }
+    }
+
+    #[test]
+    fn test_strip_dimers() {
+        // the number of domino tilings of the 8 x 8 board
+        let matching_poly = strip_matching_polynomial(8, 8, Boundary::Free);
+        assert_eq!(matching_poly[0], BigUint::from(12988816u64));
+        // and of the 2 x n strip, which is a Fibonacci number
+        let matching_poly = strip_matching_polynomial(2, 100, Boundary::Free);
+        let fibonacci = (0..100).fold((BigUint::one(), BigUint::one()), |(a, b), _| (b.clone(), a + b)).0;
+        assert_eq!(matching_poly[0], fibonacci);
+    }
+}
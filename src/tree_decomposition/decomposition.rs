@@ -0,0 +1,184 @@
+use std::collections::BTreeSet;
+
+/// A tree decomposition of a graph on nodes 0..node_count: a tree whose nodes
+/// are bags of graph nodes, such that every graph node and every graph edge
+/// lies in some bag, and the bags holding any one node form a subtree. The
+/// tree edges index into `bags`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeDecomposition {
+    pub bags: Vec<Vec<usize>>,
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl TreeDecomposition {
+    /// Builds a decomposition from the min-fill elimination ordering: we
+    /// repeatedly eliminate the node whose neighbours need the fewest extra
+    /// edges to become a clique (ties going to the lower degree), add those
+    /// edges, and make the node with its neighbours a bag. Each bag is hung
+    /// from the bag of the first of its neighbours to be eliminated after it.
+    pub fn min_fill(node_count: usize, edges: &[(usize, usize)]) -> TreeDecomposition {
+        let mut neighbours = vec![BTreeSet::new(); node_count];
+        for (u, v) in edges {
+            if u != v {
+                neighbours[*u].insert(*v);
+                neighbours[*v].insert(*u);
+            }
+        }
+
+        let mut eliminated = vec![false; node_count];
+        let mut order = Vec::with_capacity(node_count);
+        let mut bags = Vec::with_capacity(node_count);
+        for _ in 0..node_count {
+            let node = (0..node_count)
+                .filter(|node| !eliminated[*node])
+                .min_by_key(|node| (fill_in(&neighbours, *node), neighbours[*node].len(), *node))
+                .unwrap();
+            let node_neighbours = neighbours[node].iter().copied().collect::<Vec<_>>();
+            for (i, u) in node_neighbours.iter().enumerate() {
+                for v in node_neighbours[i + 1..].iter() {
+                    neighbours[*u].insert(*v);
+                    neighbours[*v].insert(*u);
+                }
+                neighbours[*u].remove(&node);
+            }
+            let mut bag = vec![node];
+            bag.extend(node_neighbours);
+            bags.push(bag);
+            eliminated[node] = true;
+            order.push(node);
+        }
+
+        let mut position = vec![0; node_count];
+        for (i, node) in order.iter().enumerate() {
+            position[*node] = i;
+        }
+        let mut tree_edges = Vec::new();
+        for (i, bag) in bags.iter().enumerate().take(node_count.saturating_sub(1)) {
+            // the bag's other nodes were all eliminated later; with none, the
+            // bag is hung from the next one so that the forest is a tree
+            let parent = bag[1..]
+                .iter()
+                .map(|node| position[*node])
+                .min()
+                .unwrap_or(i + 1);
+            tree_edges.push((parent, i));
+        }
+        TreeDecomposition {
+            bags,
+            edges: tree_edges,
+        }
+    }
+
+    /// The width of the decomposition, one less than its largest bag.
+    pub fn width(&self) -> usize {
+        self.bags
+            .iter()
+            .map(|bag| bag.len())
+            .max()
+            .unwrap_or(0)
+            .saturating_sub(1)
+    }
+
+    /// Checks that this is a tree decomposition of the given graph.
+    pub fn is_valid(&self, node_count: usize, edges: &[(usize, usize)]) -> bool {
+        let bag_count = self.bags.len();
+        if bag_count == 0 {
+            return node_count == 0
+        }
+        if self.edges.len() != bag_count - 1 || self.edges.iter().any(|(a, b)| *a >= bag_count || *b >= bag_count) {
+            return false
+        }
+        let bag_sets = self.bags
+            .iter()
+            .map(|bag| bag.iter().copied().collect::<BTreeSet<_>>())
+            .collect::<Vec<_>>();
+        if bag_sets.iter().flatten().any(|node| *node >= node_count) {
+            return false
+        }
+
+        // a tree: bag_count - 1 edges and connected
+        let mut tree_neighbours = vec![Vec::new(); bag_count];
+        for (a, b) in self.edges.iter() {
+            tree_neighbours[*a].push(*b);
+            tree_neighbours[*b].push(*a);
+        }
+        if connected_bags(&tree_neighbours, &vec![true; bag_count]) != bag_count {
+            return false
+        }
+
+        // every edge covered
+        if !edges.iter().all(|(u, v)| bag_sets.iter().any(|bag| bag.contains(u) && bag.contains(v))) {
+            return false
+        }
+
+        // the bags holding each node are present and connected
+        (0..node_count).all(|node| {
+            let holding = bag_sets.iter().map(|bag| bag.contains(&node)).collect::<Vec<_>>();
+            let count = holding.iter().filter(|x| **x).count();
+            count > 0 && connected_bags(&tree_neighbours, &holding) == count
+        })
+    }
+}
+
+/// the number of edges that eliminating the node would add
+fn fill_in(neighbours: &[BTreeSet<usize>], node: usize) -> usize {
+    let node_neighbours = neighbours[node].iter().collect::<Vec<_>>();
+    let mut count = 0;
+    for (i, u) in node_neighbours.iter().enumerate() {
+        for v in node_neighbours[i + 1..].iter() {
+            if !neighbours[**u].contains(v) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+/// the size of the connected part of the selected bags that contains the
+/// first selected one
+fn connected_bags(tree_neighbours: &[Vec<usize>], selected: &[bool]) -> usize {
+    let start = match selected.iter().position(|x| *x) {
+        Some(start) => start,
+        None => return 0,
+    };
+    let mut seen = vec![false; selected.len()];
+    seen[start] = true;
+    let mut stack = vec![start];
+    let mut count = 0;
+    while let Some(bag) = stack.pop() {
+        count += 1;
+        for next in tree_neighbours[bag].iter() {
+            if selected[*next] && !seen[*next] {
+                seen[*next] = true;
+                stack.push(*next);
+            }
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_fill() {
+        // a cycle on six nodes has treewidth 2
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (0, 5)];
+        let decomposition = TreeDecomposition::min_fill(6, &edges);
+        assert!(decomposition.is_valid(6, &edges));
+        assert_eq!(decomposition.width(), 2);
+
+        // and a tree has treewidth 1, even with an isolated node on the side
+        let edges = [(0, 1), (0, 2), (2, 3), (2, 4)];
+        let decomposition = TreeDecomposition::min_fill(6, &edges);
+        assert!(decomposition.is_valid(6, &edges));
+        assert_eq!(decomposition.width(), 1);
+
+        let broken = TreeDecomposition {
+            bags: vec![vec![0, 1], vec![1, 2], vec![0, 2]],
+            edges: vec![(0, 1), (1, 2)],
+        };
+        assert!(!broken.is_valid(3, &[(0, 1), (1, 2), (0, 2)]));
+    }
+}
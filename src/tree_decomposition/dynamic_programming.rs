@@ -0,0 +1,326 @@
+use crate::binary_graph_matching::BinaryGraph;
+use crate::weighted_graph_matching::WeightedGraph;
+use crate::traits::Graph;
+use crate::tree_decomposition::TreeDecomposition;
+use num::{BigUint, Zero, One, ToPrimitive};
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::ops::Mul;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+const POLY_SIZE: usize = size_of::<usize>()*8;
+
+/// Calculates the matching polynomial of a graph on nodes 0..node_count by
+/// dynamic programming over a tree decomposition of it, with exact
+/// coefficients indexed by the power of x. The cost is exponential only in
+/// the width of the decomposition, so this handles graphs far beyond the 64
+/// nodes of a BinaryGraph as long as they are thin enough.
+pub fn tree_decomposition_matching_polynomial(node_count: usize, edges: &[(usize, usize)],
+                                              decomposition: &TreeDecomposition) -> Vec<BigUint> {
+    let weighted_edges = edges
+        .iter()
+        .map(|(u, v)| (*u, *v, BigUint::one()))
+        .collect::<Vec<_>>();
+    matching_polynomial_over_bags(node_count, &weighted_edges, decomposition)
+}
+
+/// The weighted version of `tree_decomposition_matching_polynomial`: each
+/// k-matching adds the product of its edge weights to the coefficient of
+/// x^(n - 2k), as in the recursion of the weighted engines.
+pub fn tree_decomposition_weighted_matching_polynomial(node_count: usize, weighted_edges: &[(usize, usize, f64)],
+                                                       decomposition: &TreeDecomposition) -> Vec<f64> {
+    matching_polynomial_over_bags(node_count, weighted_edges, decomposition)
+}
+
+/// Calculates the matching polynomial of a BinaryGraph over a min-fill tree
+/// decomposition, in the same form as `calculate_matching_polynomial_pointer`.
+pub fn calculate_matching_polynomial_tree_decomposition(graph: BinaryGraph) -> [u64; POLY_SIZE] {
+    let (node_count, edges) = relabelled_edges(&graph);
+    let decomposition = TreeDecomposition::min_fill(node_count, &edges);
+    let coefficients = tree_decomposition_matching_polynomial(node_count, &edges, &decomposition);
+    let mut poly = [0; POLY_SIZE];
+    for (power, coefficient) in coefficients.iter().enumerate() {
+        poly[power] = coefficient.to_u64().expect("the coefficients of a BinaryGraph fit in a u64");
+    }
+    poly
+}
+
+/// Calculates the weighted matching polynomial of a WeightedGraph over a
+/// min-fill tree decomposition, indexed by the power of x.
+pub fn calculate_weighted_matching_polynomial_tree_decomposition(weighted_graph: &WeightedGraph) -> Vec<f64> {
+    let initial_graph_size = weighted_graph.graph.initial_graph_size();
+    let node_mask = weighted_graph.graph.node_mask();
+    let (node_count, edges) = relabelled_edges(&weighted_graph.graph);
+    let nodes = (0..MAX_NODES)
+        .filter(|node| node_mask & (1 << node) != 0)
+        .collect::<Vec<_>>();
+    let weighted_edges = edges
+        .iter()
+        .map(|(u, v)| (*u, *v, weighted_graph.weights[nodes[*u] * initial_graph_size + nodes[*v]] as f64))
+        .collect::<Vec<_>>();
+    let decomposition = TreeDecomposition::min_fill(node_count, &edges);
+    tree_decomposition_weighted_matching_polynomial(node_count, &weighted_edges, &decomposition)
+}
+
+/// the edges of the graph with the remaining nodes relabelled 0..n
+fn relabelled_edges(graph: &BinaryGraph) -> (usize, Vec<(usize, usize)>) {
+    let node_mask = graph.node_mask();
+    let mut labels = [0; MAX_NODES];
+    let mut node_count = 0;
+    for (node, label) in labels.iter_mut().enumerate() {
+        if node_mask & (1 << node) != 0 {
+            *label = node_count;
+            node_count += 1;
+        }
+    }
+    let edges = graph
+        .edges()
+        .into_iter()
+        .map(|(u, v)| (labels[u], labels[v]))
+        .collect();
+    (node_count, edges)
+}
+
+/// The partial matchings of the part of the graph below a bag, keyed by the
+/// mask of the bag's nodes (by position in the bag) that they cover, with
+/// their generating polynomial by number of edges.
+type BagTable<T> = HashMap<usize, Vec<T>>;
+
+/// The dynamic programme itself. Each edge is handled at one bag that holds
+/// both of its ends. Working up from the leaves, a bag's table starts from
+/// the empty matching, takes in each child's table (forgetting the child's
+/// nodes that are not in the bag, and keeping only combinations that cover
+/// disjoint sets of nodes), and then adds its own edges one at a time. At the
+/// root the states are summed up.
+fn matching_polynomial_over_bags<T>(node_count: usize, weighted_edges: &[(usize, usize, T)],
+                                    decomposition: &TreeDecomposition) -> Vec<T>
+where T: Clone + Zero + One + Mul<Output = T> {
+    let unweighted_edges = weighted_edges
+        .iter()
+        .map(|(u, v, _)| (*u, *v))
+        .collect::<Vec<_>>();
+    assert!(decomposition.is_valid(node_count, &unweighted_edges), "not a tree decomposition of the graph");
+    let mut matching_poly = vec![T::zero(); node_count + 1];
+    if node_count == 0 {
+        matching_poly[0] = T::one();
+        return matching_poly
+    }
+    let bags = &decomposition.bags;
+    assert!(decomposition.width() < MAX_NODES, "the bags are kept in masks of {} bits", MAX_NODES);
+
+    // hand each edge to the first bag holding both ends
+    let positions = bags
+        .iter()
+        .map(|bag| bag.iter().enumerate().map(|(i, node)| (*node, i)).collect::<HashMap<_, _>>())
+        .collect::<Vec<_>>();
+    let mut bag_edges = vec![Vec::new(); bags.len()];
+    for (u, v, weight) in weighted_edges {
+        let bag = positions
+            .iter()
+            .position(|position| position.contains_key(u) && position.contains_key(v))
+            .unwrap();
+        bag_edges[bag].push((positions[bag][u], positions[bag][v], weight.clone()));
+    }
+
+    // root the tree at the last bag, and order the bags so that children
+    // come before their parents
+    let mut tree_neighbours = vec![Vec::new(); bags.len()];
+    for (a, b) in decomposition.edges.iter() {
+        tree_neighbours[*a].push(*b);
+        tree_neighbours[*b].push(*a);
+    }
+    let root = bags.len() - 1;
+    let mut parent = vec![None; bags.len()];
+    let mut order = vec![root];
+    let mut seen = vec![false; bags.len()];
+    seen[root] = true;
+    let mut i = 0;
+    while i < order.len() {
+        let bag = order[i];
+        for next in tree_neighbours[bag].iter() {
+            if !seen[*next] {
+                seen[*next] = true;
+                parent[*next] = Some(bag);
+                order.push(*next);
+            }
+        }
+        i += 1;
+    }
+
+    let mut tables: Vec<Option<BagTable<T>>> = vec![None; bags.len()];
+    for bag in order.into_iter().rev() {
+        let mut table: BagTable<T> = HashMap::from([(0, vec![T::one()])]);
+        for child in tree_neighbours[bag].iter().filter(|child| parent[**child] == Some(bag)) {
+            let child_table = forget(tables[*child].take().unwrap(), &bags[*child], &positions[bag]);
+            table = join(&table, &child_table);
+        }
+        for (u, v, weight) in bag_edges[bag].iter() {
+            let covered = (1 << u) | (1 << v);
+            let additions = table
+                .iter()
+                .filter(|(mask, _)| **mask & covered == 0)
+                .map(|(mask, poly)| (mask | covered, poly.clone()))
+                .collect::<Vec<_>>();
+            for (mask, poly) in additions {
+                let entry = table.entry(mask).or_default();
+                add_shifted(entry, &poly, weight);
+            }
+        }
+        tables[bag] = Some(table);
+    }
+
+    for poly in tables[root].take().unwrap().into_values() {
+        for (k, coefficient) in poly.into_iter().enumerate() {
+            matching_poly[node_count - 2 * k] = matching_poly[node_count - 2 * k].clone() + coefficient;
+        }
+    }
+    matching_poly
+}
+
+/// re-expresses a child's table in terms of the positions in its parent's
+/// bag, summing over the coverage of the nodes the parent does not hold
+fn forget<T: Clone + Zero>(child_table: BagTable<T>, child_bag: &[usize],
+                           parent_positions: &HashMap<usize, usize>) -> BagTable<T> {
+    let mut table: BagTable<T> = HashMap::new();
+    for (mask, poly) in child_table {
+        let mut parent_mask = 0;
+        for (i, node) in child_bag.iter().enumerate() {
+            if mask & (1 << i) != 0 {
+                if let Some(position) = parent_positions.get(node) {
+                    parent_mask |= 1 << position;
+                }
+            }
+        }
+        let entry = table.entry(parent_mask).or_default();
+        if entry.len() < poly.len() {
+            entry.resize(poly.len(), T::zero());
+        }
+        for (k, coefficient) in poly.into_iter().enumerate() {
+            entry[k] = entry[k].clone() + coefficient;
+        }
+    }
+    table
+}
+
+/// combines two tables over the same bag, keeping only the pairs of states
+/// that cover disjoint nodes and multiplying their polynomials
+fn join<T: Clone + Zero + Mul<Output = T>>(table: &BagTable<T>, other: &BagTable<T>) -> BagTable<T> {
+    let mut joined: BagTable<T> = HashMap::new();
+    for (mask, poly) in table.iter() {
+        for (other_mask, other_poly) in other.iter() {
+            if mask & other_mask != 0 {
+                continue
+            }
+            let entry = joined.entry(mask | other_mask).or_default();
+            let length = poly.len() + other_poly.len() - 1;
+            if entry.len() < length {
+                entry.resize(length, T::zero());
+            }
+            for (i, x) in poly.iter().enumerate() {
+                for (j, y) in other_poly.iter().enumerate() {
+                    entry[i + j] = entry[i + j].clone() + x.clone() * y.clone();
+                }
+            }
+        }
+    }
+    joined
+}
+
+/// entry += weight * y * poly, i.e. the matchings in poly with one more edge
+fn add_shifted<T: Clone + Zero + Mul<Output = T>>(entry: &mut Vec<T>, poly: &[T], weight: &T) {
+    if entry.len() < poly.len() + 1 {
+        entry.resize(poly.len() + 1, T::zero());
+    }
+    for (k, coefficient) in poly.iter().enumerate() {
+        entry[k + 1] = entry[k + 1].clone() + weight.clone() * coefficient.clone();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_matching_polynomial_pointer;
+    use crate::transfer_matrix::{Boundary, strip_matching_polynomial};
+    use crate::weighted_graph_matching::_calculate_weighted_matching_polynomial_binary;
+
+    #[test]
+    fn test_tree_decomposition_against_pointer() {
+        let standard_data = [
+            0b11001, 0b1001, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        let fc_data = [
+            0b111111, 0b11111, 0b1111, 0b111, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        // the standard graph with node 2 removed
+        let standard_data_missing = [
+            0b11001, 0b1001, 0, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0,
+        ];
+        for (data, graph_size) in [(standard_data, 5), (fc_data, 6), (standard_data_missing, 5)] {
+            let graph = BinaryGraph::from_graph_subset(data, graph_size);
+            assert_eq!(calculate_matching_polynomial_tree_decomposition(graph), calculate_matching_polynomial_pointer(graph));
+        }
+    }
+
+    #[test]
+    fn test_tree_decomposition_large_grid() {
+        // the 3 x 40 grid has 120 nodes but treewidth 3
+        let (width, length) = (3, 40);
+        let mut edges = Vec::new();
+        for column in 0..length {
+            for row in 0..width {
+                let node = column * width + row;
+                if row + 1 < width {
+                    edges.push((node, node + 1));
+                }
+                if column + 1 < length {
+                    edges.push((node, node + width));
+                }
+            }
+        }
+        let decomposition = TreeDecomposition::min_fill(width * length, &edges);
+        assert!(decomposition.width() <= 4);
+        assert_eq!(tree_decomposition_matching_polynomial(width * length, &edges, &decomposition),
+                   strip_matching_polynomial(width, length, Boundary::Free));
+    }
+
+    #[test]
+    fn test_tree_decomposition_user_supplied() {
+        // the 4-cycle 0-1-2-3 with the decomposition {0, 1, 2} - {0, 2, 3}
+        let edges = [(0, 1), (1, 2), (2, 3), (0, 3)];
+        let decomposition = TreeDecomposition {
+            bags: vec![vec![0, 1, 2], vec![0, 2, 3]],
+            edges: vec![(0, 1)],
+        };
+        let matching_poly = tree_decomposition_matching_polynomial(4, &edges, &decomposition);
+        assert_eq!(matching_poly, [2u64, 0, 4, 0, 1].map(BigUint::from).to_vec());
+    }
+
+    #[test]
+    fn test_weighted_tree_decomposition() {
+        let standard_data = [
+            0b11001, 0b1001, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        let mut weights = [0.0; MAX_NODES*MAX_NODES];
+        for (i, weight) in weights.iter_mut().enumerate().take(25) {
+            *weight = (i % 7) as f32 * 0.5 + 0.25;
+        }
+        let weighted_graph = WeightedGraph::from(standard_data, weights);
+        let matching_poly = calculate_weighted_matching_polynomial_tree_decomposition(&weighted_graph);
+        let weighted_poly = _calculate_weighted_matching_polynomial_binary(weighted_graph);
+        assert_eq!(matching_poly.len(), weighted_poly.data().len());
+        for (x, y) in matching_poly.iter().zip(weighted_poly.data().iter()) {
+            assert!((x - *y as f64).abs() < 1e-4);
+        }
+    }
+}
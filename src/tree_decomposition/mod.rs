@@ -0,0 +1,5 @@
+mod decomposition;
+mod dynamic_programming;
+
+pub use self::decomposition::TreeDecomposition;
+pub use self::dynamic_programming::{tree_decomposition_matching_polynomial, tree_decomposition_weighted_matching_polynomial, calculate_matching_polynomial_tree_decomposition, calculate_weighted_matching_polynomial_tree_decomposition};
@@ -0,0 +1,188 @@
+use crate::binary_graph_matching::BinaryGraph;
+use crate::weighted_graph_matching::WeightedGraph;
+use crate::traits::Graph;
+use crate::polynomials::complete_matching_counts;
+use std::mem::size_of;
+
+const MAX_NODES: usize = size_of::<usize>()*8;
+const POLY_SIZE: usize = size_of::<usize>()*8;
+
+/// Calculates the weighted matching polynomial, as in
+/// `_calculate_weighted_matching_polynomial_binary`, switching to the
+/// complementary weights on dense subgraphs; see
+/// `calculate_weighted_matching_polynomial_adaptive_with_threshold`.
+pub fn calculate_weighted_matching_polynomial_adaptive(weighted_graph: &WeightedGraph) -> [f64; POLY_SIZE] {
+    calculate_weighted_matching_polynomial_adaptive_with_threshold(weighted_graph, 0.5)
+}
+
+/// The weighted version of `calculate_matching_polynomial_adaptive`. For a
+/// subgraph H on m nodes, take c to be the most common non-zero edge weight
+/// and set w'(e) = c - w(e) on every pair of nodes, with w(e) = 0 off the
+/// edges. Writing each w(e) of a matching as c - w'(e) and expanding gives
+///     Q(H) = sum_S (-1)^|S| w'(S) T_(m - 2|S|)
+/// over the matchings S of the graph of pairs with w'(e) != 0, where
+///     T_m(x) = sum_k m! / (2^k k! (m - 2k)!) c^k x^(m - 2k)
+/// is the polynomial of K_m with every weight c, a scaled Hermite polynomial.
+/// When the fraction of pairs with weight c reaches `density_threshold`,
+/// that graph is the sparser one, and we switch to it for the rest of the
+/// path. For an unweighted graph, c = 1 and this is the usual complement.
+///
+/// The coefficients are signless and indexed by the power of x, and the
+/// work is done in f64.
+pub fn calculate_weighted_matching_polynomial_adaptive_with_threshold(weighted_graph: &WeightedGraph, density_threshold: f32) -> [f64; POLY_SIZE] {
+    let initial_graph_size = weighted_graph.graph.initial_graph_size();
+    let weights = weighted_graph.weights[..initial_graph_size * initial_graph_size]
+        .iter()
+        .map(|x| *x as f64)
+        .collect::<Vec<_>>();
+    let matching_counts = complete_matching_counts::<f64>(initial_graph_size);
+    let mut poly = [0.0; POLY_SIZE];
+    _calculate_weighted_matching_polynomial_adaptive(weighted_graph.graph, &weights, 1.0, density_threshold, &matching_counts, &mut poly);
+    poly
+}
+
+fn _calculate_weighted_matching_polynomial_adaptive(graph: BinaryGraph, weights: &[f64], factor: f64, density_threshold: f32, matching_counts: &[Vec<f64>], poly: &mut [f64; POLY_SIZE]) {
+    if graph.edgeless() {
+        poly[graph.edgeless_node_count()] += factor;
+    } else if let Some((common_weight, complement_graph, complement_weights)) = complement_if_dense(graph, weights, density_threshold) {
+        _calculate_weighted_matching_polynomial_complement(complement_graph, &complement_weights, common_weight, factor, matching_counts, poly);
+    } else {
+        let (start_node, end_node, _) = graph.get_relevant_edge();
+        let weight = weights[start_node * graph.initial_graph_size() + end_node];
+        let (graph_prime, graph_prime_prime) = graph.get_graph_primes();
+        _calculate_weighted_matching_polynomial_adaptive(graph_prime_prime, weights, factor * weight, density_threshold, matching_counts, poly);
+        _calculate_weighted_matching_polynomial_adaptive(graph_prime, weights, factor, density_threshold, matching_counts, poly);
+    }
+}
+
+/// the recursion on the complementary weights, where deleting the nodes of an
+/// edge carries the factor -w'(e) and a leaf on m nodes contributes T_m
+fn _calculate_weighted_matching_polynomial_complement(graph: BinaryGraph, weights: &[f64], common_weight: f64, factor: f64, matching_counts: &[Vec<f64>], poly: &mut [f64; POLY_SIZE]) {
+    if graph.edgeless() {
+        let graph_size = graph.graph_size();
+        let mut power = factor;
+        for (k, count) in matching_counts[graph_size].iter().enumerate() {
+            poly[graph_size - 2 * k] += power * count;
+            power *= common_weight;
+        }
+    } else {
+        let (start_node, end_node, _) = graph.get_relevant_edge();
+        let weight = weights[start_node * graph.initial_graph_size() + end_node];
+        let (graph_prime, graph_prime_prime) = graph.get_graph_primes();
+        _calculate_weighted_matching_polynomial_complement(graph_prime_prime, weights, common_weight, -factor * weight, matching_counts, poly);
+        _calculate_weighted_matching_polynomial_complement(graph_prime, weights, common_weight, factor, matching_counts, poly);
+    }
+}
+
+/// If the most common non-zero edge weight c covers at least the threshold
+/// fraction of the pairs of nodes, returns c with the graph of pairs for which
+/// c - w(e) is non-zero, and those complementary weights.
+fn complement_if_dense(graph: BinaryGraph, weights: &[f64], density_threshold: f32) -> Option<(f64, BinaryGraph, Vec<f64>)> {
+    let initial_graph_size = graph.initial_graph_size();
+    let neighbourhoods = graph.neighbourhoods();
+    let nodes = (0..initial_graph_size)
+        .filter(|node| graph.node_mask() & (1 << node) != 0)
+        .collect::<Vec<_>>();
+    let pair_weight = |u: usize, v: usize| {
+        if neighbourhoods[u] & (1 << v) != 0 { weights[u * initial_graph_size + v] } else { 0.0 }
+    };
+
+    let mut edge_weights = Vec::new();
+    for (i, u) in nodes.iter().enumerate() {
+        for v in nodes[i + 1..].iter() {
+            let weight = pair_weight(*u, *v);
+            if weight != 0.0 {
+                edge_weights.push(weight);
+            }
+        }
+    }
+    edge_weights.sort_by(|a, b| a.total_cmp(b));
+    let (common_weight, count) = edge_weights
+        .chunk_by(|a, b| a == b)
+        .map(|run| (run[0], run.len()))
+        .max_by_key(|(_, count)| *count)?;
+    let pair_count = nodes.len() * (nodes.len() - 1) / 2;
+    if (count as f32) < density_threshold * pair_count as f32 {
+        return None
+    }
+
+    let mut data = [0; MAX_NODES];
+    let mut complement_weights = vec![0.0; weights.len()];
+    for (i, u) in nodes.iter().enumerate() {
+        data[*u] = 1 << (initial_graph_size - u - 1);
+        for v in nodes[i + 1..].iter() {
+            let weight = common_weight - pair_weight(*u, *v);
+            if weight != 0.0 {
+                data[*u] |= 1 << (initial_graph_size - v - 1);
+                complement_weights[u * initial_graph_size + v] = weight;
+            }
+        }
+    }
+    Some((common_weight, BinaryGraph::from_graph_subset(data, initial_graph_size), complement_weights))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weighted_graph_matching::_calculate_weighted_matching_polynomial_binary;
+    use crate::matrix_functions::weighted_graph_from_matrix;
+    use crate::calculate_matching_polynomial_pointer;
+
+    fn assert_agrees(weighted_graph: WeightedGraph, adaptive_poly: &[f64]) {
+        let weighted_poly = _calculate_weighted_matching_polynomial_binary(weighted_graph);
+        for (i, coefficient) in weighted_poly.data().iter().enumerate() {
+            let coefficient = *coefficient as f64;
+            assert!((adaptive_poly[i] - coefficient).abs() <= 1e-4 * coefficient.abs().max(1.0),
+                    "{:?} {:?}", &adaptive_poly[..weighted_poly.data().len()], weighted_poly.data());
+        }
+    }
+
+    #[test]
+    fn test_adaptive_complete_graphs() {
+        let n = 6;
+        // mostly 2.0, so that the complementary weights are sparse
+        let mut matrix = vec![2.0; n * n];
+        for (i, j, w) in [(0, 1, 0.5), (2, 4, -1.0), (3, 5, 3.0)] {
+            matrix[i * n + j] = w;
+            matrix[j * n + i] = w;
+        }
+        let weighted_graph = weighted_graph_from_matrix(&matrix, n);
+        assert_agrees(weighted_graph, &calculate_weighted_matching_polynomial_adaptive(&weighted_graph));
+
+        // arbitrary weights never switch at the default threshold, but give
+        // the same answer when made to
+        let matrix = (0..n * n)
+            .map(|k| {
+                let (i, j) = (k / n, k % n);
+                ((i + j) * 7 % 5) as f64 * 0.5 + (i * j) as f64 * 0.1
+            })
+            .collect::<Vec<_>>();
+        let weighted_graph = weighted_graph_from_matrix(&matrix, n);
+        assert_agrees(weighted_graph, &calculate_weighted_matching_polynomial_adaptive(&weighted_graph));
+        assert_agrees(weighted_graph, &calculate_weighted_matching_polynomial_adaptive_with_threshold(&weighted_graph, 0.0));
+    }
+
+    #[test]
+    fn test_adaptive_unit_weights() {
+        // with unit weights this is the unweighted engine, signless
+        let standard_data = [
+            0b11001, 0b1001, 0b110, 0b11, 0b1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0,
+        ];
+        let graph = BinaryGraph::from(standard_data);
+        let mut weights = [0.0; MAX_NODES*MAX_NODES];
+        for (u, v) in graph.edges() {
+            weights[u * 5 + v] = 1.0;
+        }
+        let weighted_graph = WeightedGraph { graph, weights };
+        let pointer_poly = calculate_matching_polynomial_pointer(graph);
+        for threshold in [0.0, 0.5, 1.0] {
+            let adaptive_poly = calculate_weighted_matching_polynomial_adaptive_with_threshold(&weighted_graph, threshold);
+            for i in 0..=5 {
+                assert_eq!(adaptive_poly[i], pointer_poly[i] as f64);
+            }
+        }
+    }
+}
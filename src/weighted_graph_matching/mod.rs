@@ -3,8 +3,10 @@ mod weighted_polynomial_calculation;
 mod visualisation;
 mod binary_weighted_polynomial;
 mod address_weighted_polynomial;
+mod adaptive_weighted_polynomial;
 
 pub use self::address_weighted_polynomial::{weighted_matching_polynomial_addresses, weighted_matching_polynomial_from_addresses, weight_from_address};
 pub use self::binary_weighted_polynomial::{_calculate_weighted_matching_polynomial_binary};
-pub use self::weighted_graphs::{WeightedGraph, get_weighted_deck};
+pub use self::adaptive_weighted_polynomial::{calculate_weighted_matching_polynomial_adaptive, calculate_weighted_matching_polynomial_adaptive_with_threshold};
+pub use self::weighted_graphs::{WeightedGraph, get_weighted_deck, get_weighted_edge_deck};
 pub use self::weighted_polynomial_calculation::{weighted_coefficient_calculation, weighted_polynomial_calculation};
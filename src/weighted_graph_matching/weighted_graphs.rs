@@ -64,3 +64,15 @@ pub fn get_weighted_deck(weighted_graph: &WeightedGraph) -> Vec<WeightedGraph> {
     }
     deck
 }
+
+pub fn get_weighted_edge_deck(weighted_graph: &WeightedGraph) -> Vec<WeightedGraph> {
+    let mut deck = Vec::<WeightedGraph>::new();
+    let graph_size = weighted_graph.graph.initial_graph_size();
+    for (node1, node2) in weighted_graph.graph.edges() {
+        // the weights can stay as they are, since the edge itself is gone
+        let mut current_graph = weighted_graph.graph;
+        current_graph.remove_edge(node1, node2, graph_size);
+        deck.push(WeightedGraph{graph: current_graph, weights: weighted_graph.weights});
+    }
+    deck
+}